@@ -1,7 +1,7 @@
 // benches/even_split_benchmark.rs
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use batch_maestro::even_split;
+use rsbatch_maestro::even_split;
 
 fn benchmark_even_split(c: &mut Criterion) {
     c.bench_function("even_split 1000000 items", |b| {
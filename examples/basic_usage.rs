@@ -1,6 +1,6 @@
 // examples/basic_usage.rs
 
-use rseven_splitter::even_split;
+use rsbatch_maestro::even_split;
 
 fn main() {
     let total_items = 100;
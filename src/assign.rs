@@ -0,0 +1,681 @@
+//! Strategies that assign individual items, rather than counts, to batches.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{split_by_count, BatchError};
+
+/// The largest number of bins accepted by [`split_exact_balanced`].
+pub const MAX_EXACT_BINS: usize = 8;
+
+/// The largest number of items accepted by [`split_exact_balanced`].
+pub const MAX_EXACT_ITEMS: usize = 12;
+
+/// Assigns weighted items to bins so that the heaviest bin is as light as possible.
+///
+/// This is the optimal multiway number partition, found by dynamic programming over subsets of
+/// items: for every subset and bin count it records the smallest achievable maximum bin weight.
+/// The work grows as `num_bins * 3^n` for `n` items, so the input is limited to
+/// [`MAX_EXACT_ITEMS`] items and [`MAX_EXACT_BINS`] bins. Unlike greedy heuristics, the result is
+/// always optimal; ties between equally good partitions are resolved deterministically.
+///
+/// # Arguments
+///
+/// * `weights` - The weight of each item.
+/// * `num_bins` - The number of bins to fill.
+///
+/// # Returns
+///
+/// A `Result` containing, for each bin, the indices of the items assigned to it in ascending
+/// order. Bins may be empty when there are fewer items than bins.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The weights slice is empty.
+/// * The number of bins is zero.
+/// * The input exceeds `MAX_EXACT_ITEMS` items or `MAX_EXACT_BINS` bins.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_exact_balanced;
+///
+/// let bins = split_exact_balanced(&[3, 3, 2, 2, 2], 2).unwrap();
+/// assert_eq!(bins, vec![vec![0, 1], vec![2, 3, 4]]);
+/// ```
+pub fn split_exact_balanced(weights: &[usize], num_bins: usize) -> Result<Vec<Vec<usize>>, BatchError> {
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if num_bins == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if weights.len() > MAX_EXACT_ITEMS {
+        return Err(BatchError::InvalidInput(format!("Exact partitioning supports at most {} items", MAX_EXACT_ITEMS)));
+    }
+    if num_bins > MAX_EXACT_BINS {
+        return Err(BatchError::InvalidInput(format!("Exact partitioning supports at most {} bins", MAX_EXACT_BINS)));
+    }
+
+    let subsets = 1usize << weights.len();
+    let mut subset_sums = vec![0usize; subsets];
+    for mask in 1..subsets {
+        let lowest = mask.trailing_zeros() as usize;
+        subset_sums[mask] = subset_sums[mask & (mask - 1)].saturating_add(weights[lowest]);
+    }
+
+    // best[j][mask]: smallest maximum bin weight when the items in `mask` fill `j + 1` bins.
+    // last_bin[j][mask]: the items placed in the last of those bins to achieve it.
+    let mut best = vec![subset_sums.clone()];
+    let mut last_bin = vec![(0..subsets).collect::<Vec<_>>()];
+    for j in 1..num_bins {
+        let mut layer = vec![usize::MAX; subsets];
+        let mut choice = vec![0; subsets];
+        for mask in 0..subsets {
+            let mut bin = mask;
+            loop {
+                let cost = best[j - 1][mask ^ bin].max(subset_sums[bin]);
+                if cost < layer[mask] {
+                    layer[mask] = cost;
+                    choice[mask] = bin;
+                }
+                if bin == 0 {
+                    break;
+                }
+                bin = (bin - 1) & mask;
+            }
+        }
+        best.push(layer);
+        last_bin.push(choice);
+    }
+
+    let mut bins = vec![Vec::new(); num_bins];
+    let mut mask = subsets - 1;
+    for j in (0..num_bins).rev() {
+        let bin = last_bin[j][mask];
+        bins[j] = (0..weights.len()).filter(|&i| bin & (1 << i) != 0).collect();
+        mask ^= bin;
+    }
+
+    Ok(bins)
+}
+
+/// Counts the edges whose endpoints are assigned to different batches.
+///
+/// Items that appear in no batch are treated as belonging to a batch of their own.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::cut_edges;
+///
+/// assert_eq!(cut_edges(&[vec![0, 1], vec![2, 3]], &[(0, 1), (1, 2), (2, 3)]), 1);
+/// ```
+pub fn cut_edges(batches: &[Vec<usize>], edges: &[(usize, usize)]) -> usize {
+    let items = batches.iter().flatten().map(|&i| i + 1).max().unwrap_or(0);
+    let mut batch_of = vec![usize::MAX; items];
+    for (b, batch) in batches.iter().enumerate() {
+        for &i in batch {
+            batch_of[i] = b;
+        }
+    }
+    let lookup = |i: usize| batch_of.get(i).copied().unwrap_or(usize::MAX);
+    edges
+        .iter()
+        .filter(|&&(a, b)| a != b && (lookup(a) != lookup(b) || lookup(a) == usize::MAX))
+        .count()
+}
+
+/// Assigns items to balanced batches while keeping dependent items together.
+///
+/// This is a greedy heuristic, not an optimal graph partition. Batch sizes follow
+/// `split_by_count(total, num_batches)`. Each batch is grown from the lowest-indexed unassigned
+/// item by repeatedly adding the unassigned item with the most dependency edges into the batch
+/// (ties go to the lowest index) until the batch is full, which tends to keep connected items
+/// co-located and reduces the number of edges crossing batch boundaries. Use [`cut_edges`] to
+/// measure the resulting cut. The running time is `O(total^2 + edges)`.
+///
+/// # Arguments
+///
+/// * `total` - The number of items, indexed `0..total`.
+/// * `num_batches` - The number of batches to produce.
+/// * `edges` - The dependency edges between items, treated as undirected.
+///
+/// # Returns
+///
+/// A `Result` containing, for each batch, the indices of its items in the order they were added.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero or exceeds the total.
+/// * Any edge endpoint lies outside `0..total`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{cut_edges, split_min_cut};
+///
+/// let edges = [(0, 2), (1, 3)];
+/// let batches = split_min_cut(4, 2, &edges).unwrap();
+/// assert_eq!(batches, vec![vec![0, 2], vec![1, 3]]);
+/// assert_eq!(cut_edges(&batches, &edges), 0);
+/// ```
+pub fn split_min_cut(total: usize, num_batches: usize, edges: &[(usize, usize)]) -> Result<Vec<Vec<usize>>, BatchError> {
+    if edges.iter().any(|&(a, b)| a >= total || b >= total) {
+        return Err(BatchError::InvalidInput(String::from("Edge endpoints must lie within 0..total")));
+    }
+    let capacities = split_by_count(total, num_batches)?;
+
+    let mut neighbors = vec![Vec::new(); total];
+    for &(a, b) in edges {
+        if a != b {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+
+    let mut assigned = vec![false; total];
+    let mut batches = Vec::with_capacity(num_batches);
+    for capacity in capacities {
+        let mut gain = vec![0usize; total];
+        let mut batch = Vec::with_capacity(capacity.get());
+        while batch.len() < capacity.get() {
+            let mut next = None;
+            for item in (0..total).filter(|&i| !assigned[i]) {
+                if next.is_none_or(|best: usize| gain[item] > gain[best]) {
+                    next = Some(item);
+                }
+            }
+            let item = next.expect("capacities sum to the number of items");
+            assigned[item] = true;
+            batch.push(item);
+            for &neighbor in &neighbors[item] {
+                gain[neighbor] += 1;
+            }
+        }
+        batches.push(batch);
+    }
+
+    Ok(batches)
+}
+
+/// Splits `0..total` into balanced batches that never separate the items of a group.
+///
+/// Every group of item indices is treated as one indivisible unit, and every index that belongs
+/// to no group is a unit of its own. The split starts with `ceil(total / max_batch_size)`
+/// batches and places the units largest-first, each into the currently lightest batch that still
+/// has room, opening an extra batch only when no existing batch can take a unit. This keeps batch
+/// sizes close to each other and at most `max_batch_size`.
+///
+/// # Arguments
+///
+/// * `total` - The number of items, indexed `0..total`.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `groups` - Sets of item indices that must land in the same batch.
+///
+/// # Returns
+///
+/// A `Result` containing, for each batch, its item indices in ascending order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * Any group index lies outside `0..total` or appears in more than one group.
+/// * Any group is larger than `max_batch_size`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_keeping_together;
+///
+/// let batches = split_keeping_together(6, 3, &[vec![0, 5]]).unwrap();
+/// assert_eq!(batches, vec![vec![0, 3, 5], vec![1, 2, 4]]);
+/// ```
+pub fn split_keeping_together(total: usize, max_batch_size: usize, groups: &[Vec<usize>]) -> Result<Vec<Vec<usize>>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let mut grouped = vec![false; total];
+    let mut units: Vec<Vec<usize>> = Vec::with_capacity(total);
+    for group in groups.iter().filter(|group| !group.is_empty()) {
+        if group.len() > max_batch_size {
+            return Err(BatchError::ImpossibleConstraint(String::from("Groups must not be larger than the max batch size")));
+        }
+        for &i in group {
+            if i >= total {
+                return Err(BatchError::InvalidInput(String::from("Group indices must lie within 0..total")));
+            }
+            if grouped[i] {
+                return Err(BatchError::InvalidInput(String::from("Groups must be disjoint")));
+            }
+            grouped[i] = true;
+        }
+        units.push(group.clone());
+    }
+    units.extend((0..total).filter(|&i| !grouped[i]).map(|i| vec![i]));
+    units.sort_by_key(|unit| core::cmp::Reverse(unit.len()));
+
+    let mut batches: Vec<Vec<usize>> = vec![Vec::new(); total.div_ceil(max_batch_size)];
+    for unit in units {
+        let lightest = batches
+            .iter()
+            .enumerate()
+            .filter(|(_, batch)| batch.len() + unit.len() <= max_batch_size)
+            .min_by_key(|(_, batch)| batch.len())
+            .map(|(b, _)| b);
+        match lightest {
+            Some(b) => batches[b].extend(unit),
+            None => batches.push(unit),
+        }
+    }
+    for batch in &mut batches {
+        batch.sort_unstable();
+    }
+
+    Ok(batches)
+}
+
+/// Packs items of known sizes into as few bins of a fixed capacity as first-fit decreasing
+/// finds.
+///
+/// The items are placed largest first (equal sizes in their original order), each into the
+/// first bin that still has room for it, and a new bin is opened only when none does.
+/// First-fit decreasing is a heuristic: it never uses more than `11/9` of the optimal number of
+/// bins plus one, but is not always optimal.
+///
+/// # Arguments
+///
+/// * `items` - The size of each item.
+/// * `capacity` - The largest total size a bin can hold.
+///
+/// # Returns
+///
+/// A `Result` containing, for each bin in the order it was opened, the sizes of its items in
+/// the order they were placed. An empty `items` slice yields no bins.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The capacity is zero.
+/// * Any single item is larger than the capacity.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::bin_pack;
+///
+/// let bins = bin_pack(&[4, 8, 1, 4, 2, 1], 10).unwrap();
+/// assert_eq!(bins, vec![vec![8, 2], vec![4, 4, 1, 1]]);
+/// ```
+pub fn bin_pack(items: &[usize], capacity: usize) -> Result<Vec<Vec<usize>>, BatchError> {
+    if capacity == 0 {
+        return Err(BatchError::InvalidInput(String::from("Bin capacity must be a positive number")));
+    }
+    if let Some(index) = items.iter().position(|&size| size > capacity) {
+        return Err(BatchError::ImpossibleConstraint(format!(
+            "Item {} of size {} exceeds the bin capacity of {}",
+            index, items[index], capacity
+        )));
+    }
+
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by(|&a, &b| items[b].cmp(&items[a]));
+
+    let mut bins: Vec<Vec<usize>> = Vec::new();
+    let mut free: Vec<usize> = Vec::new();
+    for index in order {
+        let size = items[index];
+        match free.iter().position(|&room| room >= size) {
+            Some(bin) => {
+                bins[bin].push(size);
+                free[bin] -= size;
+            }
+            None => {
+                bins.push(vec![size]);
+                free.push(capacity - size);
+            }
+        }
+    }
+
+    Ok(bins)
+}
+
+/// How [`assignment_vector`] and [`assign_bitsets`] distribute items across workers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssignMode {
+    /// Each worker receives one contiguous block of items, sized by `split_by_count`.
+    Contiguous,
+    /// Item `i` goes to worker `i % num_workers`.
+    RoundRobin,
+}
+
+/// Returns the worker index of every item.
+///
+/// # Arguments
+///
+/// * `total` - The number of items to assign.
+/// * `num_workers` - The number of workers.
+/// * `mode` - How the items are distributed.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of length `total` whose entry `i` is the worker of item `i`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The num_workers is zero.
+/// * The total is smaller than num_workers in contiguous mode.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{assignment_vector, AssignMode};
+///
+/// assert_eq!(assignment_vector(5, 2, AssignMode::Contiguous).unwrap(), vec![0, 0, 0, 1, 1]);
+/// assert_eq!(assignment_vector(5, 2, AssignMode::RoundRobin).unwrap(), vec![0, 1, 0, 1, 0]);
+/// ```
+pub fn assignment_vector(total: usize, num_workers: usize, mode: AssignMode) -> Result<Vec<usize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_workers == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+
+    match mode {
+        AssignMode::Contiguous => {
+            let batch_sizes = split_by_count(total, num_workers)?;
+            Ok(batch_sizes.iter().enumerate().flat_map(|(worker, size)| core::iter::repeat_n(worker, size.get())).collect())
+        }
+        AssignMode::RoundRobin => Ok((0..total).map(|item| item % num_workers).collect()),
+    }
+}
+
+/// Returns the item indices owned by each worker under round-robin assignment.
+///
+/// Item `i` goes to worker `i % num_workers`, as in [`AssignMode::RoundRobin`], so every worker's
+/// indices are increasing and the workers' counts differ by at most one. When there are more
+/// workers than items, the trailing workers receive empty lists.
+///
+/// # Arguments
+///
+/// * `total` - The number of items to assign.
+/// * `num_workers` - The number of workers.
+///
+/// # Returns
+///
+/// A `Result` containing one list of item indices per worker.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The num_workers is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::round_robin_assign;
+///
+/// assert_eq!(round_robin_assign(7, 3).unwrap(), vec![vec![0, 3, 6], vec![1, 4], vec![2, 5]]);
+/// ```
+pub fn round_robin_assign(total: usize, num_workers: usize) -> Result<Vec<Vec<usize>>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_workers == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+
+    Ok((0..num_workers).map(|worker| (worker..total).step_by(num_workers).collect()).collect())
+}
+
+/// Returns the assignment of items to workers as one bitset per worker.
+///
+/// Bit `i` of a worker's bitset (bit `i % 64` of word `i / 64`) is set when item `i` belongs to
+/// that worker, using the same distribution as [`assignment_vector`]. Each bitset holds
+/// `ceil(total / 64)` words, which is far more compact than per-worker index lists for large
+/// totals. Use [`bitset_contains`] to query membership.
+///
+/// # Arguments
+///
+/// * `total` - The number of items to assign.
+/// * `num_workers` - The number of workers.
+/// * `mode` - How the items are distributed.
+///
+/// # Returns
+///
+/// A `Result` containing one bitset per worker.
+///
+/// # Errors
+///
+/// Returns the same errors as [`assignment_vector`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{assign_bitsets, bitset_contains, AssignMode};
+///
+/// let bitsets = assign_bitsets(100, 3, AssignMode::RoundRobin).unwrap();
+/// assert!(bitset_contains(&bitsets[1], 70));
+/// assert!(!bitset_contains(&bitsets[0], 70));
+/// ```
+pub fn assign_bitsets(total: usize, num_workers: usize, mode: AssignMode) -> Result<Vec<Vec<u64>>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_workers == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+
+    let mut bitsets = vec![vec![0u64; total.div_ceil(64)]; num_workers];
+    let mut set = |worker: usize, item: usize| bitsets[worker][item / 64] |= 1 << (item % 64);
+    match mode {
+        AssignMode::Contiguous => {
+            let mut start = 0;
+            for (worker, size) in split_by_count(total, num_workers)?.into_iter().enumerate() {
+                (start..start + size.get()).for_each(|item| set(worker, item));
+                start += size.get();
+            }
+        }
+        AssignMode::RoundRobin => (0..total).for_each(|item| set(item % num_workers, item)),
+    }
+
+    Ok(bitsets)
+}
+
+/// Returns whether bit `index` is set in `bitset`; indices past the end are not set.
+pub fn bitset_contains(bitset: &[u64], index: usize) -> bool {
+    bitset.get(index / 64).is_some_and(|word| word & (1 << (index % 64)) != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_assign() {
+        for total in 1..40 {
+            for num_workers in 1..12 {
+                let lists = round_robin_assign(total, num_workers).unwrap();
+                assert_eq!(lists.len(), num_workers);
+                let mut items: Vec<usize> = lists.concat();
+                items.sort_unstable();
+                assert_eq!(items, (0..total).collect::<Vec<_>>());
+                let counts: Vec<usize> = lists.iter().map(Vec::len).collect();
+                assert!(counts.iter().max().unwrap() - counts.iter().min().unwrap() <= 1);
+                let owners = assignment_vector(total, num_workers, AssignMode::RoundRobin).unwrap();
+                assert!(lists.iter().enumerate().all(|(worker, list)| list.iter().all(|&item| owners[item] == worker)));
+            }
+        }
+        assert_eq!(round_robin_assign(0, 3), Err(BatchError::ZeroTotal));
+        assert_eq!(round_robin_assign(3, 0), Err(BatchError::ZeroBatchCount));
+    }
+
+    fn max_load(weights: &[usize], bins: &[Vec<usize>]) -> usize {
+        bins.iter().map(|bin| bin.iter().map(|&i| weights[i]).sum::<usize>()).max().unwrap()
+    }
+
+    #[test]
+    fn test_split_exact_balanced_beats_greedy() {
+        let weights = [3, 3, 2, 2, 2];
+
+        // Longest-processing-time greedy: heaviest item first into the lightest bin.
+        let mut greedy = [0usize; 2];
+        for &w in &weights {
+            let lightest = if greedy[0] <= greedy[1] { 0 } else { 1 };
+            greedy[lightest] += w;
+        }
+        assert_eq!(greedy.iter().max(), Some(&7));
+
+        let bins = split_exact_balanced(&weights, 2).unwrap();
+        assert_eq!(max_load(&weights, &bins), 6);
+    }
+
+    #[test]
+    fn test_split_exact_balanced_assigns_every_item_once() {
+        let weights = [8, 7, 6, 5, 4, 3, 2, 1, 9, 10];
+        let bins = split_exact_balanced(&weights, 3).unwrap();
+        let mut assigned: Vec<usize> = bins.concat();
+        assigned.sort_unstable();
+        assert_eq!(assigned, (0..weights.len()).collect::<Vec<_>>());
+        assert_eq!(max_load(&weights, &bins), 19);
+
+        let bins = split_exact_balanced(&[5, 1], 4).unwrap();
+        assert_eq!(bins.len(), 4);
+        assert_eq!(max_load(&[5, 1], &bins), 5);
+    }
+
+    #[test]
+    fn test_split_exact_balanced_errors() {
+        assert!(split_exact_balanced(&[], 2).is_err());
+        assert!(split_exact_balanced(&[1, 2], 0).is_err());
+        assert!(split_exact_balanced(&[1; MAX_EXACT_ITEMS + 1], 2).is_err());
+        assert!(split_exact_balanced(&[1, 2], MAX_EXACT_BINS + 1).is_err());
+    }
+
+    #[test]
+    fn test_split_min_cut_beats_contiguous() {
+        let edges = [(0, 4), (1, 5), (2, 6), (3, 7), (0, 1), (6, 7)];
+        let contiguous = vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]];
+        assert_eq!(cut_edges(&contiguous, &edges), 4);
+
+        let batches = split_min_cut(8, 2, &edges).unwrap();
+        assert!(cut_edges(&batches, &edges) < cut_edges(&contiguous, &edges));
+        assert_eq!(cut_edges(&batches, &edges), 0);
+        assert!(batches.iter().all(|batch| batch.len() == 4));
+    }
+
+    #[test]
+    fn test_split_min_cut_assigns_every_item_once() {
+        let edges = [(0, 9), (3, 4), (4, 5), (9, 2)];
+        let batches = split_min_cut(10, 3, &edges).unwrap();
+        assert_eq!(batches.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 3, 3]);
+        let mut assigned: Vec<usize> = batches.concat();
+        assigned.sort_unstable();
+        assert_eq!(assigned, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_min_cut_errors() {
+        assert!(split_min_cut(0, 2, &[]).is_err());
+        assert!(split_min_cut(4, 0, &[]).is_err());
+        assert!(split_min_cut(4, 5, &[]).is_err());
+        assert!(split_min_cut(4, 2, &[(0, 4)]).is_err());
+    }
+
+    #[test]
+    fn test_split_keeping_together() {
+        let groups = vec![vec![0, 7, 13], vec![2, 3], vec![19, 1, 10, 11]];
+        let batches = split_keeping_together(20, 5, &groups).unwrap();
+
+        for group in &groups {
+            let holders: Vec<usize> = (0..batches.len()).filter(|&b| group.iter().any(|i| batches[b].contains(i))).collect();
+            assert_eq!(holders.len(), 1);
+        }
+        assert_eq!(batches.len(), 4);
+        assert!(batches.iter().all(|batch| batch.len() == 5));
+        let mut assigned = batches.concat();
+        assigned.sort_unstable();
+        assert_eq!(assigned, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_keeping_together_opens_extra_batch() {
+        let batches = split_keeping_together(6, 3, &[vec![0, 1], vec![2, 3], vec![4, 5]]).unwrap();
+        assert_eq!(batches, vec![vec![0, 1], vec![2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_split_keeping_together_errors() {
+        assert!(split_keeping_together(0, 3, &[]).is_err());
+        assert!(split_keeping_together(6, 0, &[]).is_err());
+        assert!(split_keeping_together(6, 3, &[vec![1, 6]]).is_err());
+        assert!(split_keeping_together(6, 3, &[vec![1, 2], vec![2, 3]]).is_err());
+        assert!(split_keeping_together(6, 3, &[vec![1, 1]]).is_err());
+        assert!(split_keeping_together(6, 3, &[vec![0, 1, 2, 3]]).is_err());
+    }
+
+    #[test]
+    fn test_bin_pack() {
+        let items = [7, 3, 9, 1, 4, 4, 6, 2, 8, 5, 0, 10];
+        for capacity in 10..30 {
+            let bins = bin_pack(&items, capacity).unwrap();
+            assert!(bins.iter().all(|bin| bin.iter().sum::<usize>() <= capacity));
+            let mut packed: Vec<usize> = bins.concat();
+            let mut expected = items.to_vec();
+            packed.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(packed, expected);
+            let lower_bound = items.iter().sum::<usize>().div_ceil(capacity);
+            assert!(bins.len() <= lower_bound * 11 / 9 + 1);
+        }
+        assert_eq!(bin_pack(&[5, 5, 5, 5], 10).unwrap(), vec![vec![5, 5], vec![5, 5]]);
+        assert_eq!(bin_pack(&[], 10).unwrap(), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn test_bin_pack_errors() {
+        assert!(matches!(bin_pack(&[1, 2], 0), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(bin_pack(&[1, 11, 2], 10), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_assign_bitsets_match_assignment_vector() {
+        for mode in [AssignMode::Contiguous, AssignMode::RoundRobin] {
+            let bitsets = assign_bitsets(150, 4, mode).unwrap();
+            let assignment = assignment_vector(150, 4, mode).unwrap();
+            assert_eq!(bitsets.len(), 4);
+            for (item, &worker) in assignment.iter().enumerate() {
+                for (w, bitset) in bitsets.iter().enumerate() {
+                    assert_eq!(bitset_contains(bitset, item), w == worker);
+                }
+            }
+            assert_eq!(bitsets.iter().flatten().map(|word| word.count_ones() as usize).sum::<usize>(), 150);
+            assert!(!bitset_contains(&bitsets[0], 150 * 64));
+        }
+    }
+
+    #[test]
+    fn test_assignment_errors() {
+        assert!(assignment_vector(0, 2, AssignMode::RoundRobin).is_err());
+        assert!(assignment_vector(5, 0, AssignMode::RoundRobin).is_err());
+        assert!(assignment_vector(2, 5, AssignMode::Contiguous).is_err());
+        assert!(assign_bitsets(0, 2, AssignMode::RoundRobin).is_err());
+        assert!(assign_bitsets(5, 0, AssignMode::Contiguous).is_err());
+        assert!(assign_bitsets(2, 5, AssignMode::Contiguous).is_err());
+    }
+}
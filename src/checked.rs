@@ -0,0 +1,85 @@
+//! The core splitting functions, returning `Option` instead of `Result`.
+//!
+//! Each function here calls its counterpart at the crate root and discards the error, returning
+//! `None` whenever the counterpart would fail. This suits callers that only branch on success.
+//!
+//! ```
+//! use rsbatch_maestro::checked;
+//!
+//! assert_eq!(checked::even_split(50, 8).map(|(n, _)| n), Some(10));
+//! assert_eq!(checked::even_split(0, 8), None);
+//! ```
+
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+/// [`crate::even_split`], returning `None` on failure.
+pub fn even_split(total: usize, max_batch_size: usize) -> Option<(usize, Vec<NonZeroUsize>)> {
+    crate::even_split(total, max_batch_size).ok()
+}
+
+/// [`crate::count_batches`], returning `None` on failure.
+pub fn count_batches(total: usize, max_batch_size: usize) -> Option<usize> {
+    crate::count_batches(total, max_batch_size).ok()
+}
+
+/// [`crate::split_weighted`], returning `None` on failure.
+pub fn split_weighted(total: usize, weights: Vec<usize>) -> Option<Vec<NonZeroUsize>> {
+    crate::split_weighted(total, weights).ok()
+}
+
+/// [`crate::split_range`], returning `None` on failure.
+pub fn split_range(total: usize, min_batch_size: usize, max_batch_size: usize) -> Option<Vec<(usize, usize, usize)>> {
+    crate::split_range(total, min_batch_size, max_batch_size).ok()
+}
+
+/// [`crate::optimize_split`], returning `None` on failure.
+pub fn optimize_split(total: usize, min_batches: usize, max_batches: usize) -> Option<(usize, Vec<NonZeroUsize>)> {
+    crate::optimize_split(total, min_batches, max_batches).ok()
+}
+
+/// [`crate::split_with_min_batch`], returning `None` on failure.
+pub fn split_with_min_batch(total: usize, max_batch_size: usize, min_batch_size: usize) -> Option<(usize, Vec<NonZeroUsize>)> {
+    crate::split_with_min_batch(total, max_batch_size, min_batch_size).ok()
+}
+
+/// [`crate::split_with_min_and_max`], returning `None` on failure.
+pub fn split_with_min_and_max(total: usize, min_batch_size: usize, max_batch_size: usize) -> Option<Vec<NonZeroUsize>> {
+    crate::split_with_min_and_max(total, min_batch_size, max_batch_size).ok()
+}
+
+/// [`crate::split_near_target`], returning `None` on failure.
+pub fn split_near_target(total: usize, target: usize) -> Option<Vec<NonZeroUsize>> {
+    crate::split_near_target(total, target).ok()
+}
+
+/// [`crate::split_by_count`], returning `None` on failure.
+pub fn split_by_count(total: usize, num_batches: usize) -> Option<Vec<NonZeroUsize>> {
+    crate::split_by_count(total, num_batches).ok()
+}
+
+/// [`crate::split_with_remainder`], returning `None` on failure.
+pub fn split_with_remainder(total: usize, max_batch_size: usize) -> Option<(usize, Vec<NonZeroUsize>, usize)> {
+    crate::split_with_remainder(total, max_batch_size).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_result_versions() {
+        for (total, max) in [(0, 8), (50, 8), (10, 0), (7, 3)] {
+            assert_eq!(even_split(total, max), crate::even_split(total, max).ok());
+            assert_eq!(count_batches(total, max), crate::count_batches(total, max).ok());
+            assert_eq!(split_by_count(total, max), crate::split_by_count(total, max).ok());
+            assert_eq!(split_with_remainder(total, max), crate::split_with_remainder(total, max).ok());
+            assert_eq!(split_near_target(total, max), crate::split_near_target(total, max).ok());
+        }
+        assert_eq!(split_weighted(10, vec![1, 0]), None);
+        assert_eq!(split_range(100, 40, 20), None);
+        assert_eq!(optimize_split(100, 5, 3), None);
+        assert_eq!(split_with_min_batch(7, 5, 4), None);
+        assert_eq!(split_with_min_and_max(23, 4, 5).map(|sizes| sizes.len()), Some(5));
+    }
+}
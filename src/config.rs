@@ -0,0 +1,272 @@
+//! Builders that describe a split before it is computed.
+
+use alloc::format;
+use core::num::NonZeroUsize;
+
+use crate::{split_by_count, split_by_count_with, BatchError, BatchPlan, RemainderStrategy};
+
+/// The constraints of a split, collected before choosing how many batches to produce.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::BatchConfig;
+///
+/// let [a, b, c] = BatchConfig::new(10).max_batch_size(4).exact::<3>().into_batches().unwrap();
+/// assert_eq!((a.get(), b.get(), c.get()), (4, 3, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    total: usize,
+    max_batch_size: Option<usize>,
+}
+
+impl BatchConfig {
+    /// Creates a configuration for splitting `total` with no batch size limit.
+    pub fn new(total: usize) -> Self {
+        BatchConfig { total, max_batch_size: None }
+    }
+
+    /// Limits every batch to at most `max_batch_size`.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Fixes the number of batches to `N`, carried in the type of the returned builder.
+    pub fn exact<const N: usize>(self) -> TypedBatchBuilder<N> {
+        TypedBatchBuilder { config: self }
+    }
+}
+
+/// A [`BatchConfig`] whose batch count `N` is known at compile time.
+///
+/// [`into_batches`](TypedBatchBuilder::into_batches) returns a fixed-size array, so callers can
+/// destructure the batches without checking their number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedBatchBuilder<const N: usize> {
+    config: BatchConfig,
+}
+
+impl<const N: usize> TypedBatchBuilder<N> {
+    /// Splits the total into exactly `N` balanced batches, as computed by `split_by_count`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * The total is zero.
+    /// * N is zero.
+    /// * The max_batch_size is zero.
+    /// * The total cannot be covered by `N` batches of at most max_batch_size.
+    /// * The total is smaller than `N`.
+    pub fn into_batches(self) -> Result<[NonZeroUsize; N], BatchError> {
+        let BatchConfig { total, max_batch_size } = self.config;
+        if total == 0 {
+            return Err(BatchError::ZeroTotal);
+        }
+        if N == 0 {
+            return Err(BatchError::ZeroBatchCount);
+        }
+        if let Some(max_batch_size) = max_batch_size {
+            if max_batch_size == 0 {
+                return Err(BatchError::ZeroMaxBatchSize);
+            }
+            if total.div_ceil(max_batch_size) > N {
+                return Err(BatchError::ImpossibleConstraint(format!("Cannot fit a total of {} into {} batches of at most {}", total, N, max_batch_size)));
+            }
+        }
+        if total < N {
+            return Err(BatchError::InsufficientTotal { total, min_required: N });
+        }
+
+        let batch_sizes = split_by_count(total, N)?;
+        Ok(batch_sizes.try_into().expect("split_by_count returns exactly N batches"))
+    }
+}
+
+/// A fluent builder that combines size and count constraints into a single split.
+///
+/// Every constraint left unset is unbounded. [`build`](SplitBuilder::build) uses the fewest
+/// batches that keep every size at or below the maximum, balances them according to the
+/// remainder strategy, and reports an error when the constraints cannot all be met.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{RemainderStrategy, SplitBuilder};
+///
+/// let plan = SplitBuilder::new()
+///     .total(100)
+///     .min_batch_size(20)
+///     .max_batch_size(30)
+///     .max_batches(4)
+///     .remainder_strategy(RemainderStrategy::Back)
+///     .build()
+///     .unwrap();
+/// assert_eq!(plan.sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![25, 25, 25, 25]);
+/// assert!(SplitBuilder::new().total(100).max_batch_size(20).max_batches(4).build().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SplitBuilder {
+    total: usize,
+    min_batch_size: Option<usize>,
+    max_batch_size: Option<usize>,
+    max_batches: Option<usize>,
+    remainder_strategy: RemainderStrategy,
+}
+
+impl SplitBuilder {
+    /// Creates a builder with a total of zero and no constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the total to split.
+    pub fn total(mut self, total: usize) -> Self {
+        self.total = total;
+        self
+    }
+
+    /// Requires every batch to hold at least `min_batch_size`.
+    pub fn min_batch_size(mut self, min_batch_size: usize) -> Self {
+        self.min_batch_size = Some(min_batch_size);
+        self
+    }
+
+    /// Limits every batch to at most `max_batch_size`.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = Some(max_batch_size);
+        self
+    }
+
+    /// Limits the split to at most `max_batches` batches.
+    pub fn max_batches(mut self, max_batches: usize) -> Self {
+        self.max_batches = Some(max_batches);
+        self
+    }
+
+    /// Chooses where the batches carrying one extra unit are placed.
+    pub fn remainder_strategy(mut self, remainder_strategy: RemainderStrategy) -> Self {
+        self.remainder_strategy = remainder_strategy;
+        self
+    }
+
+    /// Computes the split satisfying every constraint that was set.
+    ///
+    /// The plan covers the whole total, so its remainder is always zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * The total is zero or was never set.
+    /// * The min_batch_size, max_batch_size or max_batches is set to zero.
+    /// * The min_batch_size is greater than max_batch_size.
+    /// * The max_batch_size needs more batches than max_batches allows.
+    /// * The min_batch_size cannot be met by every batch.
+    pub fn build(self) -> Result<BatchPlan, BatchError> {
+        let SplitBuilder { total, min_batch_size, max_batch_size, max_batches, remainder_strategy } = self;
+        if total == 0 {
+            return Err(BatchError::ZeroTotal);
+        }
+        if min_batch_size == Some(0) {
+            return Err(BatchError::ZeroMinBatchSize);
+        }
+        if max_batch_size == Some(0) {
+            return Err(BatchError::ZeroMaxBatchSize);
+        }
+        if max_batches == Some(0) {
+            return Err(BatchError::ZeroBatchCount);
+        }
+        if let (Some(min), Some(max)) = (min_batch_size, max_batch_size) {
+            if min > max {
+                return Err(BatchError::MinExceedsMax { min, max });
+            }
+        }
+
+        let num_batches = total.div_ceil(max_batch_size.unwrap_or(total));
+        if let Some(max_batches) = max_batches {
+            if num_batches > max_batches {
+                return Err(BatchError::ImpossibleConstraint(format!(
+                    "Cannot fit a total of {} into {} batches of at most {}",
+                    total,
+                    max_batches,
+                    max_batch_size.unwrap_or(total)
+                )));
+            }
+        }
+        if let Some(min_batch_size) = min_batch_size {
+            if num_batches.checked_mul(min_batch_size).is_none_or(|required| required > total) {
+                return Err(BatchError::ImpossibleConstraint(format!(
+                    "Cannot give each of {} batches at least {} of a total of {}",
+                    num_batches, min_batch_size, total
+                )));
+            }
+        }
+
+        let sizes = split_by_count_with(total, num_batches, remainder_strategy)?;
+        Ok(BatchPlan { total, sizes, remainder: 0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_destructures() {
+        let [first, .., last] = BatchConfig::new(100).max_batch_size(16).exact::<8>().into_batches().unwrap();
+        assert_eq!((first.get(), last.get()), (13, 12));
+        let batches = BatchConfig::new(64).exact::<8>().into_batches().unwrap();
+        assert_eq!(batches, [NonZeroUsize::new(8).unwrap(); 8]);
+    }
+
+    #[test]
+    fn test_exact_infeasible() {
+        assert!(BatchConfig::new(100).max_batch_size(10).exact::<8>().into_batches().is_err());
+        assert!(BatchConfig::new(5).exact::<8>().into_batches().is_err());
+        assert!(BatchConfig::new(0).exact::<8>().into_batches().is_err());
+        assert!(BatchConfig::new(10).max_batch_size(0).exact::<8>().into_batches().is_err());
+        assert!(BatchConfig::new(10).exact::<0>().into_batches().is_err());
+    }
+
+    #[test]
+    fn test_split_builder_defaults() {
+        let plan = SplitBuilder::new().total(10).build().unwrap();
+        assert_eq!(plan.sizes, vec![NonZeroUsize::new(10).unwrap()]);
+        assert!(plan.validate().is_ok());
+        let plan = SplitBuilder::new().total(10).max_batch_size(4).build().unwrap();
+        assert_eq!(plan.sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![4, 3, 3]);
+        let plan = SplitBuilder::new().total(10).max_batch_size(4).remainder_strategy(RemainderStrategy::Back).build().unwrap();
+        assert_eq!(plan.sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![3, 3, 4]);
+    }
+
+    #[test]
+    fn test_split_builder_bounds() {
+        for total in 1..60 {
+            for min in 1..8 {
+                for max in min..10 {
+                    for max_batches in 1..12 {
+                        let built = SplitBuilder::new().total(total).min_batch_size(min).max_batch_size(max).max_batches(max_batches).build();
+                        let feasible = (1..=max_batches).any(|n| n * min <= total && total <= n * max);
+                        assert_eq!(built.is_ok(), feasible);
+                        if let Ok(plan) = built {
+                            assert!(plan.validate().is_ok());
+                            assert!(plan.sizes.len() <= max_batches);
+                            assert!(plan.sizes.iter().all(|b| (min..=max).contains(&b.get())));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_builder_errors() {
+        assert_eq!(SplitBuilder::new().build(), Err(BatchError::ZeroTotal));
+        assert_eq!(SplitBuilder::new().total(10).min_batch_size(0).build(), Err(BatchError::ZeroMinBatchSize));
+        assert_eq!(SplitBuilder::new().total(10).max_batch_size(0).build(), Err(BatchError::ZeroMaxBatchSize));
+        assert_eq!(SplitBuilder::new().total(10).max_batches(0).build(), Err(BatchError::ZeroBatchCount));
+        assert_eq!(SplitBuilder::new().total(10).min_batch_size(5).max_batch_size(4).build(), Err(BatchError::MinExceedsMax { min: 5, max: 4 }));
+        assert!(matches!(SplitBuilder::new().total(7).min_batch_size(4).max_batch_size(5).build(), Err(BatchError::ImpossibleConstraint(_))));
+    }
+}
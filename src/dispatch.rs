@@ -0,0 +1,241 @@
+//! Helpers that decorate splits with metadata needed by dispatchers.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::{even_split, BatchError};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes a sequence of integers with 64-bit FNV-1a over their little-endian `u64` encoding.
+///
+/// Unlike `std::collections::hash_map::DefaultHasher`, the output is fixed by this algorithm and
+/// does not change between processes, platforms or compiler versions.
+fn fnv1a(values: &[u64]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for value in values {
+        for byte in value.to_le_bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+    hash
+}
+
+/// Splits a total into even batches, tagging each batch with a stable identifier.
+///
+/// The batches are those produced by [`even_split`]. Each batch's ID is the 64-bit FNV-1a hash
+/// of `(total, max_batch_size, batch_index, offset)`, where `offset` is the number of items in
+/// all preceding batches, each value encoded as a little-endian `u64`. Re-running the splitter
+/// with identical inputs therefore yields identical IDs, in any process, which lets downstream
+/// consumers deduplicate re-dispatched batches for exactly-once processing.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(id, size)` pairs in batch order.
+///
+/// # Errors
+///
+/// Returns the same errors as [`even_split`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_identified;
+///
+/// let first = split_identified(50, 8).unwrap();
+/// let second = split_identified(50, 8).unwrap();
+/// assert_eq!(first, second);
+/// assert_eq!(first.len(), 10);
+/// ```
+pub fn split_identified(total: usize, max_batch_size: usize) -> Result<Vec<(u64, NonZeroUsize)>, BatchError> {
+    let (_, batch_sizes) = even_split(total, max_batch_size)?;
+
+    let mut offset = 0;
+    let mut identified = Vec::with_capacity(batch_sizes.len());
+    for (index, size) in batch_sizes.into_iter().enumerate() {
+        let id = fnv1a(&[total as u64, max_batch_size as u64, index as u64, offset as u64]);
+        identified.push((id, size));
+        offset += size.get();
+    }
+
+    Ok(identified)
+}
+
+/// The outcome of a split together with the arguments that produced it.
+///
+/// Keeping the arguments alongside the batch sizes allows the split to be logged as a
+/// reproducible command with [`SplitResult::to_command`] and re-created elsewhere via
+/// [`parse_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitResult {
+    /// The named numeric arguments passed to the splitting function, in call order.
+    pub arguments: Vec<(String, usize)>,
+    /// The resulting batch sizes.
+    pub batch_sizes: Vec<NonZeroUsize>,
+}
+
+impl SplitResult {
+    /// Creates a split result from its named arguments and batch sizes.
+    pub fn new(arguments: &[(&str, usize)], batch_sizes: Vec<NonZeroUsize>) -> Self {
+        SplitResult {
+            arguments: arguments.iter().map(|&(name, value)| (name.to_string(), value)).collect(),
+            batch_sizes,
+        }
+    }
+
+    /// Renders the split as a human-readable reproduction command.
+    ///
+    /// The output has the form `strategy(name=value, ...) -> N batches` and can be read back
+    /// with [`parse_command`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbatch_maestro::{even_split, SplitResult};
+    ///
+    /// let (_, batch_sizes) = even_split(100, 8).unwrap();
+    /// let result = SplitResult::new(&[("total", 100), ("max", 8)], batch_sizes);
+    /// assert_eq!(result.to_command("even_split"), "even_split(total=100, max=8) -> 20 batches");
+    /// ```
+    pub fn to_command(&self, strategy: &str) -> String {
+        let arguments: Vec<String> = self.arguments.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        format!("{}({}) -> {} batches", strategy, arguments.join(", "), self.batch_sizes.len())
+    }
+}
+
+/// Parses a command produced by [`SplitResult::to_command`] back into its parts.
+///
+/// The accepted grammar is:
+///
+/// ```text
+/// command   = strategy "(" [ argument { "," argument } ] ")" [ "->" summary ]
+/// argument  = name "=" value
+/// ```
+///
+/// where `strategy` and `name` are non-empty identifiers made of ASCII letters, digits and
+/// underscores, `value` is a decimal `usize`, and whitespace around tokens is ignored. The
+/// optional `-> summary` suffix is informational and is not parsed.
+///
+/// # Arguments
+///
+/// * `command` - The command string to parse.
+///
+/// # Returns
+///
+/// A `Result` containing the strategy name and its argument values in order.
+///
+/// # Errors
+///
+/// Returns an error if the command does not follow the grammar above.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::parse_command;
+///
+/// let (strategy, arguments) = parse_command("even_split(total=100, max=8) -> 20 batches").unwrap();
+/// assert_eq!(strategy, "even_split");
+/// assert_eq!(arguments, vec![100, 8]);
+/// ```
+pub fn parse_command(command: &str) -> Result<(String, Vec<usize>), BatchError> {
+    let is_identifier = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    let call = match command.split_once("->") {
+        Some((call, _)) => call.trim(),
+        None => command.trim(),
+    };
+    let (strategy, rest) = call
+        .split_once('(')
+        .ok_or_else(|| BatchError::InvalidInput(String::from("Command must contain an argument list")))?;
+    let strategy = strategy.trim();
+    if !is_identifier(strategy) {
+        return Err(BatchError::InvalidInput(format!("Invalid strategy name: {:?}", strategy)));
+    }
+    let arguments = rest
+        .strip_suffix(')')
+        .ok_or_else(|| BatchError::InvalidInput(String::from("Argument list must be closed with ')'")))?;
+
+    let mut values = Vec::new();
+    if arguments.trim().is_empty() {
+        return Ok((strategy.to_string(), values));
+    }
+    for argument in arguments.split(',') {
+        let (name, value) = argument
+            .split_once('=')
+            .ok_or_else(|| BatchError::InvalidInput(format!("Argument must have the form name=value: {:?}", argument.trim())))?;
+        if !is_identifier(name.trim()) {
+            return Err(BatchError::InvalidInput(format!("Invalid argument name: {:?}", name.trim())));
+        }
+        let value = value
+            .trim()
+            .parse()
+            .map_err(|_| BatchError::InvalidInput(format!("Invalid argument value: {:?}", value.trim())))?;
+        values.push(value);
+    }
+
+    Ok((strategy.to_string(), values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_identified_stable() {
+        assert_eq!(split_identified(100, 8), split_identified(100, 8));
+        assert_ne!(split_identified(100, 8).unwrap()[0].0, split_identified(100, 9).unwrap()[0].0);
+    }
+
+    #[test]
+    fn test_split_identified_unique() {
+        let identified = split_identified(1000, 7).unwrap();
+        let mut ids: Vec<u64> = identified.iter().map(|&(id, _)| id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), identified.len());
+        assert_eq!(identified.iter().map(|&(_, s)| s.get()).sum::<usize>(), 1000);
+    }
+
+    #[test]
+    fn test_split_identified_errors() {
+        assert!(split_identified(0, 8).is_err());
+        assert!(split_identified(10, 0).is_err());
+    }
+
+    #[test]
+    fn test_command_round_trip() {
+        let (_, batch_sizes) = even_split(100, 8).unwrap();
+        let command = SplitResult::new(&[("total", 100), ("max", 8)], batch_sizes.clone()).to_command("even_split");
+        let (strategy, arguments) = parse_command(&command).unwrap();
+        assert_eq!(strategy, "even_split");
+        assert_eq!(arguments, vec![100, 8]);
+        assert_eq!(even_split(arguments[0], arguments[1]).unwrap().1, batch_sizes);
+    }
+
+    #[test]
+    fn test_parse_command_whitespace_and_empty() {
+        assert_eq!(parse_command("  split_by_count( total = 10 ,num_batches=3 )"), Ok((String::from("split_by_count"), vec![10, 3])));
+        assert_eq!(parse_command("noop()"), Ok((String::from("noop"), vec![])));
+    }
+
+    #[test]
+    fn test_parse_command_errors() {
+        assert!(parse_command("even_split").is_err());
+        assert!(parse_command("even_split(total=100").is_err());
+        assert!(parse_command("(total=100)").is_err());
+        assert!(parse_command("even split(total=100)").is_err());
+        assert!(parse_command("even_split(100)").is_err());
+        assert!(parse_command("even_split(total=-1)").is_err());
+        assert!(parse_command("even_split(=5)").is_err());
+    }
+}
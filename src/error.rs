@@ -0,0 +1,132 @@
+//! The error type shared by all splitting strategies.
+
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt;
+
+/// The reasons a split can fail.
+///
+/// The enum is `#[non_exhaustive]`, so new failure causes can be added without breaking callers
+/// that match on it; such matches need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BatchError {
+    /// The total to split is zero.
+    ZeroTotal,
+    /// The maximum batch size is zero.
+    ZeroMaxBatchSize,
+    /// The minimum batch size is zero.
+    ZeroMinBatchSize,
+    /// The requested number of batches (or workers) is zero.
+    ZeroBatchCount,
+    /// The minimum batch size is larger than the maximum batch size.
+    MinExceedsMax {
+        /// The minimum batch size.
+        min: usize,
+        /// The maximum batch size.
+        max: usize,
+    },
+    /// The minimum number of batches is larger than the maximum number of batches.
+    MinBatchesExceedMax {
+        /// The minimum number of batches.
+        min: usize,
+        /// The maximum number of batches.
+        max: usize,
+    },
+    /// No weights were given.
+    EmptyWeights,
+    /// A weight is zero.
+    ZeroWeight {
+        /// The index of the first zero weight.
+        index: usize,
+    },
+    /// A floating-point weight is negative, infinite or NaN, or a speed is not positive.
+    InvalidWeight {
+        /// The index of the first invalid weight.
+        index: usize,
+    },
+    /// The total is too small to give every batch at least one item.
+    InsufficientTotal {
+        /// The total to split.
+        total: usize,
+        /// The smallest total that could be split.
+        min_required: usize,
+    },
+    /// The batch sizes do not add up to the total they are meant to cover.
+    SumMismatch {
+        /// The total the batches should add up to.
+        expected: usize,
+        /// The sum of the batch sizes.
+        actual: usize,
+    },
+    /// An intermediate or final value does not fit in its integer or duration type.
+    Overflow,
+    /// The arguments are valid on their own, but no split satisfies all of them together.
+    ImpossibleConstraint(String),
+    /// An argument is malformed or out of its allowed range.
+    InvalidInput(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::ZeroTotal => f.write_str("Total (0) must be a positive number"),
+            BatchError::ZeroMaxBatchSize => f.write_str("Max batch size (0) must be a positive number"),
+            BatchError::ZeroMinBatchSize => f.write_str("Minimum batch size (0) must be a positive number"),
+            BatchError::ZeroBatchCount => f.write_str("Number of batches (0) must be a positive number"),
+            BatchError::MinExceedsMax { min, max } => write!(f, "Minimum batch size ({}) must not exceed maximum batch size ({})", min, max),
+            BatchError::MinBatchesExceedMax { min, max } => {
+                write!(f, "Minimum number of batches ({}) must not exceed maximum number of batches ({})", min, max)
+            }
+            BatchError::EmptyWeights => f.write_str("Weights vector must not be empty"),
+            BatchError::ZeroWeight { index } => write!(f, "Weight at index {} is zero; all weights must be positive numbers", index),
+            BatchError::InvalidWeight { index } => write!(f, "Weight at index {} is not a finite positive number", index),
+            BatchError::InsufficientTotal { total, min_required } => {
+                write!(f, "Total ({}) is too small to give every batch at least one item; at least {} is required", total, min_required)
+            }
+            BatchError::SumMismatch { expected, actual } => write!(f, "Batch sizes add up to {}, expected the total of {}", actual, expected),
+            BatchError::Overflow => f.write_str("Arithmetic overflow"),
+            BatchError::ImpossibleConstraint(message) | BatchError::InvalidInput(message) => f.write_str(message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BatchError {}
+
+impl From<BatchError> for String {
+    fn from(error: BatchError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_includes_values() {
+        assert_eq!(BatchError::ZeroTotal.to_string(), "Total (0) must be a positive number");
+        assert_eq!(String::from(BatchError::ZeroMaxBatchSize), "Max batch size (0) must be a positive number");
+        assert_eq!(BatchError::ZeroWeight { index: 2 }.to_string(), "Weight at index 2 is zero; all weights must be positive numbers");
+        assert_eq!(BatchError::InvalidWeight { index: 1 }.to_string(), "Weight at index 1 is not a finite positive number");
+        assert_eq!(BatchError::MinExceedsMax { min: 5, max: 4 }.to_string(), "Minimum batch size (5) must not exceed maximum batch size (4)");
+        assert_eq!(
+            BatchError::MinBatchesExceedMax { min: 5, max: 3 }.to_string(),
+            "Minimum number of batches (5) must not exceed maximum number of batches (3)"
+        );
+        assert_eq!(
+            BatchError::InsufficientTotal { total: 2, min_required: 5 }.to_string(),
+            "Total (2) is too small to give every batch at least one item; at least 5 is required"
+        );
+        assert_eq!(BatchError::SumMismatch { expected: 10, actual: 8 }.to_string(), "Batch sizes add up to 8, expected the total of 10");
+        assert_eq!(BatchError::InvalidInput(String::from("Keys must be strictly increasing")).to_string(), "Keys must be strictly increasing");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_is_std_error() {
+        let error: Box<dyn std::error::Error> = Box::new(BatchError::Overflow);
+        assert_eq!(error.to_string(), "Arithmetic overflow");
+    }
+}
@@ -0,0 +1,414 @@
+//! Core splitting strategies generic over the unsigned integer type of the total.
+//!
+//! The top-level functions of the crate work on `usize`. The functions here compute the same
+//! splits for any [`BatchInt`], which is useful when totals such as byte counts are kept in `u64`
+//! on targets where `usize` is narrower. Batch counts are always `usize`, since they describe the
+//! length of the returned vector.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::num::{NonZeroU128, NonZeroU32, NonZeroU64, NonZeroUsize};
+use core::ops::{Add, Div, Rem, Sub};
+
+use crate::BatchError;
+
+/// An unsigned integer type that batch sizes can be computed in.
+///
+/// The trait exposes the arithmetic the splitting algorithms need, along with conversions to and
+/// from `usize` for batch counts and the matching `NonZero*` type for batch sizes. It is
+/// implemented for `u32`, `u64`, `u128` and `usize`.
+pub trait BatchInt: Copy + Ord + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Div<Output = Self> + Rem<Output = Self> {
+    /// The non-zero counterpart used for batch sizes, such as `NonZeroU64` for `u64`.
+    type NonZero: Copy + Ord + fmt::Debug;
+
+    /// The value zero.
+    const ZERO: Self;
+    /// The value one.
+    const ONE: Self;
+
+    /// Adds two values, returning `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Multiplies two values, returning `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Converts the value to its non-zero counterpart, returning `None` for zero.
+    fn to_non_zero(self) -> Option<Self::NonZero>;
+    /// Converts a `usize`, returning `None` if it does not fit.
+    fn from_usize(value: usize) -> Option<Self>;
+    /// Converts the value to a `usize`, returning `None` if it does not fit.
+    fn to_usize(self) -> Option<usize>;
+    /// Widens the value to a `u128`.
+    fn to_u128(self) -> u128;
+    /// Converts a `u128`, returning `None` if it does not fit.
+    fn from_u128(value: u128) -> Option<Self>;
+}
+
+macro_rules! impl_batch_int {
+    ($($int:ty => $non_zero:ty),* $(,)?) => {$(
+        impl BatchInt for $int {
+            type NonZero = $non_zero;
+
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_add(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$int>::checked_mul(self, rhs)
+            }
+
+            fn to_non_zero(self) -> Option<Self::NonZero> {
+                <$non_zero>::new(self)
+            }
+
+            fn from_usize(value: usize) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+
+            fn to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            fn from_u128(value: u128) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+        }
+    )*};
+}
+
+impl_batch_int!(u32 => NonZeroU32, u64 => NonZeroU64, u128 => NonZeroU128, usize => NonZeroUsize);
+
+/// Converts a value known to be positive into its non-zero counterpart.
+fn non_zero<T: BatchInt>(value: T) -> T::NonZero {
+    value.to_non_zero().expect("batch size is positive")
+}
+
+/// Converts a batch count to `usize`, failing if the batches could not be held in a vector.
+fn count<T: BatchInt>(value: T) -> Result<usize, BatchError> {
+    value.to_usize().ok_or(BatchError::Overflow)
+}
+
+/// Splits a total into even batches; see [`crate::even_split`].
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * The number of batches does not fit in `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::generic::even_split;
+/// use std::num::NonZeroU64;
+///
+/// let (num_batches, batch_sizes) = even_split(12_000_000_000u64, 4_000_000_000).unwrap();
+/// assert_eq!(num_batches, 3);
+/// assert_eq!(batch_sizes, vec![NonZeroU64::new(4_000_000_000).unwrap(); 3]);
+/// ```
+pub fn even_split<T: BatchInt>(total: T, max_batch_size: T) -> Result<(usize, Vec<T::NonZero>), BatchError> {
+    if total == T::ZERO {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == T::ZERO {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let batch_size = even_batch_size(total, max_batch_size);
+    let num_batches = count(total / batch_size)?;
+    Ok((num_batches, vec![non_zero(batch_size); num_batches]))
+}
+
+/// Returns the batch size `even_split` uses: the total itself when it fits in one batch,
+/// otherwise the largest divisor of the total not exceeding `max_batch_size`.
+///
+/// Callers must ensure both arguments are positive.
+pub(crate) fn even_batch_size<T: BatchInt>(total: T, max_batch_size: T) -> T {
+    if total <= max_batch_size {
+        return total;
+    }
+
+    let mut batch_size = max_batch_size;
+    while batch_size > T::ONE {
+        if total % batch_size == T::ZERO {
+            return batch_size;
+        }
+        batch_size = batch_size - T::ONE;
+    }
+    T::ONE
+}
+
+/// Splits a total into a specified number of batches; see [`crate::split_by_count`].
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero.
+/// * The total is smaller than the number of batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::generic::split_by_count;
+///
+/// let batch_sizes = split_by_count(10u32, 3).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<u32>>(), vec![4, 3, 3]);
+/// ```
+pub fn split_by_count<T: BatchInt>(total: T, num_batches: usize) -> Result<Vec<T::NonZero>, BatchError> {
+    if total == T::ZERO {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    let insufficient = BatchError::InsufficientTotal { total: total.to_usize().unwrap_or(usize::MAX), min_required: num_batches };
+    let divisor = T::from_usize(num_batches).ok_or(insufficient.clone())?;
+
+    let base_size = total / divisor;
+    let remainder = count(total % divisor)?;
+
+    let mut batches = Vec::with_capacity(num_batches);
+    for i in 0..num_batches {
+        let size = if i < remainder { base_size + T::ONE } else { base_size };
+        batches.push(size.to_non_zero().ok_or(insufficient.clone())?);
+    }
+
+    Ok(batches)
+}
+
+/// Splits a total into full batches and a separate remainder; see [`crate::split_with_remainder`].
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * The number of batches does not fit in `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::generic::split_with_remainder;
+/// use std::num::NonZeroU64;
+///
+/// let (num_batches, batch_sizes, remainder) = split_with_remainder(50u64, 8).unwrap();
+/// assert_eq!(num_batches, 6);
+/// assert_eq!(batch_sizes, vec![NonZeroU64::new(8).unwrap(); 6]);
+/// assert_eq!(remainder, 2);
+/// ```
+pub fn split_with_remainder<T: BatchInt>(total: T, max_batch_size: T) -> Result<(usize, Vec<T::NonZero>, T), BatchError> {
+    if total == T::ZERO {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == T::ZERO {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let num_batches = count(total / max_batch_size)?;
+    let remainder = total % max_batch_size;
+
+    if num_batches == 0 {
+        Ok((1, vec![non_zero(total)], T::ZERO))
+    } else {
+        Ok((num_batches, vec![non_zero(max_batch_size); num_batches], remainder))
+    }
+}
+
+/// Splits a total according to weights; see [`crate::split_weighted`].
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights vector is empty.
+/// * Any weight is zero.
+/// * The total is too small to give every batch at least one unit.
+/// * The sum of the weights overflows `T`.
+/// * Scaling the total by a weight overflows `u128`, which can only happen for `u128` totals.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::generic::split_weighted;
+///
+/// let batch_sizes = split_weighted(100u64, vec![1, 2, 3]).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<u64>>(), vec![17, 33, 50]);
+/// ```
+pub fn split_weighted<T: BatchInt>(total: T, weights: Vec<T>) -> Result<Vec<T::NonZero>, BatchError> {
+    let sizes = weighted_sizes(total, &weights)?;
+    if sizes.contains(&T::ZERO) {
+        return Err(BatchError::InsufficientTotal {
+            total: total.to_usize().unwrap_or(usize::MAX),
+            min_required: min_weighted_total(total, &weights),
+        });
+    }
+
+    Ok(sizes.into_iter().map(non_zero).collect())
+}
+
+/// Returns a total above `total` that `split_weighted` can split without empty batches, while
+/// the total just below it cannot, saturating at `usize::MAX`.
+///
+/// Every weight but the last needs `total * weight >= ceil(weight_sum / 2)` to round up to one
+/// unit, which gives a lower bound. Each of the other batches is rounded up by at most half a
+/// unit, so the last batch holds more than `total * last / weight_sum - (len - 1) / 2` and is
+/// never empty once the total exceeds `(len - 1) * weight_sum / (2 * last)`. The answer is
+/// bisected between the two bounds, so it takes a logarithmic number of trial splits.
+fn min_weighted_total<T: BatchInt>(total: T, weights: &[T]) -> usize {
+    let Some(weight_sum) = weights.iter().try_fold(T::ZERO, |sum, &weight| sum.checked_add(weight)) else {
+        return usize::MAX;
+    };
+    let weight_sum = weight_sum.to_u128();
+    let half_up = weight_sum.div_ceil(2);
+    let len = weights.len() as u128;
+    let works = |candidate: u128| match T::from_u128(candidate).map(|candidate| weighted_sizes(candidate, weights)) {
+        Some(Ok(sizes)) => !sizes.contains(&T::ZERO),
+        _ => false,
+    };
+
+    let mut low = total.to_u128().saturating_add(1).max(len);
+    for &weight in &weights[..weights.len() - 1] {
+        low = low.max(half_up.div_ceil(weight.to_u128()));
+    }
+    let last = weights[weights.len() - 1].to_u128();
+    let high = ((len - 1).saturating_mul(weight_sum) / (2 * last) + 1).max(low).min(usize::MAX as u128);
+    if low > high || !works(high) {
+        return usize::MAX;
+    }
+    if works(low) {
+        return low as usize;
+    }
+
+    // Bisect while `low` does not work and `high` does.
+    let mut high = high;
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if works(mid) {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high as usize
+}
+
+/// Computes `(total * weight + half_sum) / weight_sum` for `weight <= weight_sum`.
+///
+/// The whole multiples of `weight_sum` in the total are scaled in `T`, which cannot overflow
+/// since the result never exceeds the total. Only the part below `weight_sum` is multiplied, in
+/// `u128`, so the computation fails only when that product does not fit in `u128`.
+fn scale_rounded<T: BatchInt>(total: T, weight: T, weight_sum: T, half_sum: T) -> Result<T, BatchError> {
+    let whole = (total / weight_sum).checked_mul(weight).ok_or(BatchError::Overflow)?;
+    let fraction = (total % weight_sum)
+        .to_u128()
+        .checked_mul(weight.to_u128())
+        .and_then(|product| product.checked_add(half_sum.to_u128()))
+        .ok_or(BatchError::Overflow)?
+        / weight_sum.to_u128();
+    T::from_u128(fraction)
+        .and_then(|fraction| whole.checked_add(fraction))
+        .ok_or(BatchError::Overflow)
+}
+
+/// Computes the sizes of `split_weighted`, which may include zeros when the total is too small.
+///
+/// Every weight but the last receives its share rounded to the nearest integer, capped at what
+/// is left, and the last weight absorbs the rest, so the sizes always sum to `total`.
+pub(crate) fn weighted_sizes<T: BatchInt>(total: T, weights: &[T]) -> Result<Vec<T>, BatchError> {
+    if total == T::ZERO {
+        return Err(BatchError::ZeroTotal);
+    }
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = weights.iter().position(|&weight| weight == T::ZERO) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+
+    let weight_sum = weights
+        .iter()
+        .try_fold(T::ZERO, |sum, &weight| sum.checked_add(weight))
+        .ok_or(BatchError::Overflow)?;
+    let half_sum = weight_sum / (T::ONE + T::ONE);
+    let mut sizes = Vec::with_capacity(weights.len());
+    let mut remaining = total;
+
+    for (i, &weight) in weights.iter().enumerate() {
+        let size = if i == weights.len() - 1 {
+            remaining
+        } else {
+            scale_rounded(total, weight, weight_sum, half_sum)?.min(remaining)
+        };
+        sizes.push(size);
+        remaining = remaining - size;
+    }
+
+    Ok(sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_matches_usize() {
+        for (total, max) in [(50usize, 8usize), (17, 8), (128, 128), (3, 10)] {
+            let (count_usize, sizes_usize) = crate::even_split(total, max).unwrap();
+            let (count_u64, sizes_u64) = even_split(total as u64, max as u64).unwrap();
+            assert_eq!(count_usize, count_u64);
+            assert!(sizes_usize.iter().zip(&sizes_u64).all(|(a, b)| a.get() as u64 == b.get()));
+            assert_eq!(crate::split_by_count(total, 3).unwrap().iter().map(|b| b.get() as u32).collect::<Vec<_>>(), split_by_count(total as u32, 3).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_generic_wide_totals() {
+        let total = u128::from(u64::MAX) * 4;
+        let (num_batches, batch_sizes, remainder) = split_with_remainder(total, u128::from(u64::MAX)).unwrap();
+        assert_eq!((num_batches, remainder), (4, 0));
+        assert_eq!(batch_sizes[0].get(), u128::from(u64::MAX));
+        assert_eq!(split_by_count(total, 2).unwrap()[1].get(), total / 2);
+    }
+
+    #[test]
+    fn test_generic_errors() {
+        assert_eq!(even_split(0u64, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(split_by_count(2u32, 5), Err(BatchError::InsufficientTotal { total: 2, min_required: 5 }));
+        assert_eq!(split_weighted(10u64, vec![1, 0]), Err(BatchError::ZeroWeight { index: 1 }));
+        assert_eq!(split_weighted(u128::MAX - 1, vec![u128::MAX / 2, u128::MAX / 2 + 1]), Err(BatchError::Overflow));
+        assert_eq!(split_with_remainder(10u128, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_split_weighted_scales_without_overflow() {
+        let sizes: Vec<u32> = split_weighted(u32::MAX, vec![3, 1]).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(sizes, vec![3_221_225_471, 1_073_741_824]);
+        let sizes: Vec<u128> = split_weighted(u128::MAX, vec![1, 1]).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(sizes, vec![u128::MAX / 2 + 1, u128::MAX / 2]);
+    }
+
+    #[test]
+    fn test_split_weighted_insufficient_total() {
+        assert_eq!(split_weighted(2u64, vec![1, 1, 1]), Err(BatchError::InsufficientTotal { total: 2, min_required: 3 }));
+        assert_eq!(split_weighted(3u32, vec![1, 1, 1, 1, 1]), Err(BatchError::InsufficientTotal { total: 3, min_required: 5 }));
+        let Err(BatchError::InsufficientTotal { min_required, .. }) = split_weighted(10usize, vec![1, 100]) else {
+            panic!("expected InsufficientTotal");
+        };
+        assert!(split_weighted(min_required, vec![1, 100]).is_ok());
+        assert!(split_weighted(min_required - 1, vec![1, 100]).is_err());
+
+        let Err(BatchError::InsufficientTotal { min_required, .. }) = split_weighted(10usize, vec![1, 1 << 32, 1]) else {
+            panic!("expected InsufficientTotal");
+        };
+        assert!(split_weighted(min_required, vec![1, 1 << 32, 1]).is_ok());
+        assert!(split_weighted(min_required - 1, vec![1, 1 << 32, 1]).is_err());
+    }
+}
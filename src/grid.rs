@@ -0,0 +1,232 @@
+//! Strategies that split two-dimensional spaces into rectangular tiles.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{split_offsets, BatchError};
+
+/// A rectangular tile, given as its `(x_range, y_range)` (column range, row range).
+pub type Tile = (Range<usize>, Range<usize>);
+
+/// Returns the `(x, y)` tile coordinates and ranges of a `width × height` space cut into tiles
+/// of at most `tile_w × tile_h`, in row-major order.
+fn tiles(width: usize, height: usize, tile_w: usize, tile_h: usize) -> Vec<((usize, usize), Tile)> {
+    let mut tiles = Vec::with_capacity(width.div_ceil(tile_w) * height.div_ceil(tile_h));
+    for (ty, y) in (0..height).step_by(tile_h).enumerate() {
+        for (tx, x) in (0..width).step_by(tile_w).enumerate() {
+            tiles.push(((tx, ty), (x..(x + tile_w).min(width), y..(y + tile_h).min(height))));
+        }
+    }
+    tiles
+}
+
+/// Interleaves the bits of `x` (even positions) and `y` (odd positions) into a Morton code.
+fn morton_code(x: usize, y: usize) -> u128 {
+    let mut code = 0u128;
+    for bit in 0..usize::BITS {
+        code |= (((x >> bit) & 1) as u128) << (2 * bit);
+        code |= (((y >> bit) & 1) as u128) << (2 * bit + 1);
+    }
+    code
+}
+
+/// Splits a `width × height` space into tiles emitted in Morton (Z-order).
+///
+/// The space is cut into tiles of `tile_w × tile_h`, with narrower or shorter tiles along the
+/// right and bottom edges when the dimensions are not exact multiples. Rather than row-major
+/// order, the tiles are ordered by the Morton code of their grid coordinates, which keeps
+/// spatially close tiles close in the output and improves cache behavior for quadtree-style
+/// traversals. On a 2×2 block of tiles the order is top-left, top-right, bottom-left,
+/// bottom-right, recursively.
+///
+/// # Arguments
+///
+/// * `width` - The width of the space.
+/// * `height` - The height of the space.
+/// * `tile_w` - The width of each tile.
+/// * `tile_h` - The height of each tile.
+///
+/// # Returns
+///
+/// A `Result` containing `(x_range, y_range)` pairs, one per tile, in Z-order.
+///
+/// # Errors
+///
+/// Returns an error if any dimension or tile dimension is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_grid_zorder;
+///
+/// let tiles = split_grid_zorder(4, 4, 2, 2).unwrap();
+/// assert_eq!(tiles, vec![(0..2, 0..2), (2..4, 0..2), (0..2, 2..4), (2..4, 2..4)]);
+/// ```
+pub fn split_grid_zorder(width: usize, height: usize, tile_w: usize, tile_h: usize) -> Result<Vec<Tile>, BatchError> {
+    if width == 0 || height == 0 {
+        return Err(BatchError::InvalidInput(String::from("Grid dimensions must be positive numbers")));
+    }
+    if tile_w == 0 || tile_h == 0 {
+        return Err(BatchError::InvalidInput(String::from("Tile dimensions must be positive numbers")));
+    }
+
+    let mut tiles = tiles(width, height, tile_w, tile_h);
+    tiles.sort_by_key(|&((tx, ty), _)| morton_code(tx, ty));
+    Ok(tiles.into_iter().map(|(_, ranges)| ranges).collect())
+}
+
+/// Returns how far a `height × width` tile is from square, as a `(longer, shorter)` ratio.
+fn aspect(height: u128, width: u128) -> (u128, u128) {
+    (height.max(width), height.min(width))
+}
+
+/// Splits a `rows × cols` grid into roughly `num_tiles` balanced rectangular tiles.
+///
+/// The grid is cut into `r` row bands and `c` column bands, giving `r × c` tiles. Among the
+/// factorizations with `r <= rows` and `c <= cols`, the one whose tile count is closest to
+/// `num_tiles` wins, and ties are broken in favor of the most nearly square tiles. The row and
+/// column bands are balanced as in [`split_offsets`], so tile heights (and widths) differ by at
+/// most one, and the tiles cover the grid exactly once.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the grid.
+/// * `cols` - The number of columns in the grid.
+/// * `num_tiles` - The desired number of tiles.
+///
+/// # Returns
+///
+/// A `Result` containing `(col_range, row_range)` pairs, one per tile, in row-major order. Like
+/// every [`Tile`], the column (x) range comes first.
+///
+/// # Errors
+///
+/// Returns an error if either grid dimension or `num_tiles` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_grid;
+///
+/// let tiles = split_grid(4, 6, 6).unwrap();
+/// assert_eq!(tiles, vec![
+///     (0..2, 0..2), (2..4, 0..2), (4..6, 0..2),
+///     (0..2, 2..4), (2..4, 2..4), (4..6, 2..4),
+/// ]);
+/// ```
+pub fn split_grid(rows: usize, cols: usize, num_tiles: usize) -> Result<Vec<Tile>, BatchError> {
+    if rows == 0 || cols == 0 {
+        return Err(BatchError::InvalidInput(String::from("Grid dimensions must be positive numbers")));
+    }
+    if num_tiles == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+
+    // Each candidate is scored by (distance from num_tiles, tile aspect ratio); lower is better.
+    let mut best = (1, 1);
+    let mut best_score = ((num_tiles as u128).abs_diff(1), aspect(rows as u128, cols as u128));
+    for r in 1..=rows.min(num_tiles) {
+        let quotient = num_tiles / r;
+        for c in [quotient, quotient.saturating_add(1)] {
+            let c = c.clamp(1, cols);
+            let distance = (num_tiles as u128).abs_diff(r as u128 * c as u128);
+            // A tile is (rows / r) × (cols / c); scaling both sides by r × c keeps it integral.
+            let ratio = aspect(rows as u128 * c as u128, cols as u128 * r as u128);
+            let better_ratio = ratio.0 * best_score.1 .1 < best_score.1 .0 * ratio.1;
+            if distance < best_score.0 || (distance == best_score.0 && better_ratio) {
+                best = (r, c);
+                best_score = (distance, ratio);
+            }
+        }
+    }
+
+    let (row_bands, col_bands) = (split_offsets(rows, best.0)?, split_offsets(cols, best.1)?);
+    let mut tiles = Vec::with_capacity(row_bands.len() * col_bands.len());
+    for row_range in &row_bands {
+        for col_range in &col_bands {
+            tiles.push((col_range.clone(), row_range.clone()));
+        }
+    }
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_grid_prefers_square_tiles() {
+        let tiles = split_grid(100, 100, 4).unwrap();
+        assert_eq!(tiles, vec![(0..50, 0..50), (50..100, 0..50), (0..50, 50..100), (50..100, 50..100)]);
+
+        // 12 tiles over a 30 × 40 grid: 3 × 4 gives 10 × 10 tiles, better than 4 × 3 or 2 × 6.
+        let tiles = split_grid(30, 40, 12).unwrap();
+        assert_eq!(tiles.len(), 12);
+        assert!(tiles.iter().all(|(c, r)| r.len() == 10 && c.len() == 10));
+    }
+
+    #[test]
+    fn test_split_grid_covers_grid_once() {
+        for (rows, cols, num_tiles) in [(7, 5, 6), (1, 9, 4), (3, 3, 20), (13, 17, 7), (10, 1, 3)] {
+            let tiles = split_grid(rows, cols, num_tiles).unwrap();
+            let mut seen = vec![0; rows * cols];
+            for (col_range, row_range) in &tiles {
+                for row in row_range.clone() {
+                    for col in col_range.clone() {
+                        seen[row * cols + col] += 1;
+                    }
+                }
+            }
+            assert!(seen.iter().all(|&count| count == 1));
+        }
+    }
+
+    #[test]
+    fn test_split_grid_count_limited_by_dimensions() {
+        assert_eq!(split_grid(3, 3, 20).unwrap().len(), 9);
+        assert_eq!(split_grid(1, 9, 4).unwrap().len(), 4);
+        assert_eq!(split_grid(5, 5, 7).unwrap().len(), 6);
+        assert_eq!(split_grid(1, 1, usize::MAX), Ok(vec![(0..1, 0..1)]));
+        assert_eq!(split_grid(2, 3, usize::MAX).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_split_grid_errors() {
+        assert!(split_grid(0, 4, 2).is_err());
+        assert!(split_grid(4, 0, 2).is_err());
+        assert_eq!(split_grid(4, 4, 0), Err(BatchError::ZeroBatchCount));
+    }
+
+    #[test]
+    fn test_split_grid_zorder_sequence() {
+        let tiles = split_grid_zorder(4, 4, 1, 1).unwrap();
+        let coordinates: Vec<(usize, usize)> = tiles.iter().map(|(x, y)| (x.start, y.start)).collect();
+        assert_eq!(coordinates, vec![
+            (0, 0), (1, 0), (0, 1), (1, 1),
+            (2, 0), (3, 0), (2, 1), (3, 1),
+            (0, 2), (1, 2), (0, 3), (1, 3),
+            (2, 2), (3, 2), (2, 3), (3, 3),
+        ]);
+    }
+
+    #[test]
+    fn test_split_grid_zorder_same_tiles_as_row_major() {
+        let mut zorder = split_grid_zorder(10, 7, 3, 2).unwrap();
+        let mut row_major: Vec<_> = tiles(10, 7, 3, 2).into_iter().map(|(_, ranges)| ranges).collect();
+        assert_eq!(zorder.len(), 16);
+        let key = |(x, y): &Tile| (y.start, x.start);
+        zorder.sort_by_key(key);
+        row_major.sort_by_key(key);
+        assert_eq!(zorder, row_major);
+        assert_eq!(zorder.iter().map(|(x, y)| x.len() * y.len()).sum::<usize>(), 70);
+    }
+
+    #[test]
+    fn test_split_grid_zorder_errors() {
+        assert!(split_grid_zorder(0, 4, 1, 1).is_err());
+        assert!(split_grid_zorder(4, 0, 1, 1).is_err());
+        assert!(split_grid_zorder(4, 4, 0, 1).is_err());
+        assert!(split_grid_zorder(4, 4, 1, 0).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! Checks for the invariants that splits are expected to uphold.
+//!
+//! Each check returns `Ok(())` when the invariant holds and otherwise an `Err` describing the
+//! first violation, so the checks compose with `?` inside property tests (for example with
+//! `proptest`'s `prop_assert!`-style helpers) and with other crates' own compositions of these
+//! splitters. This module is only available with the `testing` feature.
+//!
+//! ```
+//! use rsbatch_maestro::{invariants, split_by_count};
+//!
+//! let batches = split_by_count(10, 3).unwrap();
+//! assert!(invariants::assert_covers_total(10, &batches).is_ok());
+//! assert!(invariants::assert_at_most_two_distinct_sizes(&batches).is_ok());
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::size_histogram;
+
+/// Checks that the batch sizes add up to exactly `total`.
+///
+/// # Errors
+///
+/// Returns a description of the mismatch, or of the overflow if the sum does not fit in
+/// `usize`.
+pub fn assert_covers_total(total: usize, batches: &[NonZeroUsize]) -> Result<(), String> {
+    let sum = batches
+        .iter()
+        .try_fold(0usize, |sum, size| sum.checked_add(size.get()))
+        .ok_or_else(|| format!("Batch sizes overflow usize, expected a total of {}", total))?;
+    if sum != total {
+        return Err(format!("Batch sizes add up to {}, expected {}", sum, total));
+    }
+    Ok(())
+}
+
+/// Checks that every batch size lies within `[min, max]`.
+///
+/// # Errors
+///
+/// Returns a description of the first batch outside the bounds.
+pub fn assert_within_bounds(batches: &[NonZeroUsize], min: usize, max: usize) -> Result<(), String> {
+    match batches.iter().position(|size| !(min..=max).contains(&size.get())) {
+        Some(index) => Err(format!("Batch {} has size {}, outside {}..={}", index, batches[index], min, max)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the batches have at most two distinct sizes, one apart, as balanced splits do.
+///
+/// # Errors
+///
+/// Returns a description listing the distinct sizes found.
+pub fn assert_at_most_two_distinct_sizes(batches: &[NonZeroUsize]) -> Result<(), String> {
+    let histogram = size_histogram(batches);
+    let smallest = histogram.keys().next().copied().unwrap_or(0);
+    let largest = histogram.keys().next_back().copied().unwrap_or(0);
+    if largest - smallest > 1 {
+        let sizes: Vec<usize> = histogram.into_keys().collect();
+        return Err(format!("Batch sizes {:?} are not within one of each other", sizes));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    use crate::{split_by_count, split_weighted};
+
+    fn to_batches(sizes: &[usize]) -> Vec<NonZeroUsize> {
+        sizes.iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_balanced_splits_pass() {
+        for total in 1..60 {
+            for num_batches in 1..=total {
+                let batches = split_by_count(total, num_batches).unwrap();
+                let base = total / num_batches;
+                assert_eq!(assert_covers_total(total, &batches), Ok(()));
+                assert_eq!(assert_within_bounds(&batches, base, base + 1), Ok(()));
+                assert_eq!(assert_at_most_two_distinct_sizes(&batches), Ok(()));
+            }
+        }
+        assert_eq!(assert_covers_total(0, &[]), Ok(()));
+        assert_eq!(assert_at_most_two_distinct_sizes(&[]), Ok(()));
+    }
+
+    #[test]
+    fn test_violations_are_described() {
+        assert_eq!(assert_covers_total(10, &to_batches(&[4, 4])), Err(String::from("Batch sizes add up to 8, expected 10")));
+        assert!(assert_covers_total(1, &[NonZeroUsize::MAX, NonZeroUsize::MIN]).is_err());
+        assert_eq!(assert_within_bounds(&to_batches(&[3, 5, 4]), 3, 4), Err(String::from("Batch 1 has size 5, outside 3..=4")));
+        let skewed = split_weighted(100, vec![1, 3]).unwrap();
+        assert_eq!(assert_at_most_two_distinct_sizes(&skewed), Err(String::from("Batch sizes [25, 75] are not within one of each other")));
+        assert_eq!(assert_at_most_two_distinct_sizes(&to_batches(&[2, 3, 4])), Err(String::from("Batch sizes [2, 3, 4] are not within one of each other")));
+    }
+}
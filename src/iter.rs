@@ -0,0 +1,224 @@
+//! Lazy iterators over splits, for callers that process batches one at a time.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::num::NonZeroUsize;
+
+use crate::generic::even_batch_size;
+use crate::BatchError;
+
+/// An iterator over the batches of an even split, produced by [`even_split_iter`].
+///
+/// All batches of an even split have the same size, so the iterator only stores that size and
+/// the number of batches left; nothing is allocated however many batches there are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchIterator {
+    batch_size: NonZeroUsize,
+    remaining: usize,
+}
+
+impl Iterator for BatchIterator {
+    type Item = NonZeroUsize;
+
+    fn next(&mut self) -> Option<NonZeroUsize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.batch_size)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl DoubleEndedIterator for BatchIterator {
+    fn next_back(&mut self) -> Option<NonZeroUsize> {
+        self.next()
+    }
+}
+
+impl ExactSizeIterator for BatchIterator {}
+
+impl FusedIterator for BatchIterator {}
+
+/// Splits a total into even batches lazily.
+///
+/// The iterator yields the same batches as [`even_split`](crate::even_split), using the same
+/// divisor search, but without materializing them in a vector. Its `len()` is the batch count.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing an iterator over the batch sizes.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::even_split_iter;
+///
+/// let batches = even_split_iter(50, 8).unwrap();
+/// assert_eq!(batches.len(), 10);
+/// assert!(batches.map(|b| b.get()).all(|size| size == 5));
+/// ```
+pub fn even_split_iter(total: usize, max_batch_size: usize) -> Result<BatchIterator, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let batch_size = even_batch_size(total, max_batch_size);
+    Ok(BatchIterator {
+        batch_size: NonZeroUsize::new(batch_size).unwrap(),
+        remaining: total / batch_size,
+    })
+}
+
+/// An iterator that groups the items of another iterator into balanced batches, produced by
+/// [`BatchExt::into_batches`].
+///
+/// Batch lengths follow [`split_by_count_clamped`](crate::split_by_count_clamped) for the
+/// length of the wrapped iterator, so they differ by at most one with the longer batches first.
+/// Only the current batch is collected; the wrapped iterator is consumed as batches are pulled.
+#[derive(Debug, Clone)]
+pub struct Batches<I> {
+    iter: I,
+    base_size: usize,
+    extra: usize,
+    remaining: usize,
+}
+
+impl<I: Iterator> Iterator for Batches<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let size = if self.extra > 0 {
+            self.extra -= 1;
+            self.base_size + 1
+        } else {
+            self.base_size
+        };
+        Some(self.iter.by_ref().take(size).collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for Batches<I> {}
+
+impl<I: Iterator> FusedIterator for Batches<I> {}
+
+/// Adds [`into_batches`](BatchExt::into_batches) to every `ExactSizeIterator`.
+pub trait BatchExt: ExactSizeIterator + Sized {
+    /// Groups the items into `num_batches` balanced batches, in order.
+    ///
+    /// The batch lengths are those of
+    /// [`split_by_count_clamped`](crate::split_by_count_clamped)`(self.len(), num_batches)`: when
+    /// there are fewer items than batches, each item gets its own batch, and an empty iterator
+    /// yields no batches. Concatenating the batches gives back the original sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_batches` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbatch_maestro::BatchExt;
+    ///
+    /// let batches: Vec<Vec<u32>> = (0..7).into_batches(3).collect();
+    /// assert_eq!(batches, vec![vec![0, 1, 2], vec![3, 4], vec![5, 6]]);
+    /// ```
+    fn into_batches(self, num_batches: usize) -> Batches<Self> {
+        assert!(num_batches > 0, "Number of batches must be a positive number");
+        let len = self.len();
+        let count = num_batches.min(len);
+        let base_size = len.checked_div(count).unwrap_or(0);
+        let extra = len.checked_rem(count).unwrap_or(0);
+        Batches { iter: self, base_size, extra, remaining: count }
+    }
+}
+
+impl<I: ExactSizeIterator> BatchExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    use crate::{even_split, split_by_count_clamped};
+
+    #[test]
+    fn test_even_split_iter_matches_even_split() {
+        for (total, max) in [(50, 8), (46, 8), (17, 8), (128, 128), (3, 10), (1_000_000, 7)] {
+            let (num_batches, batch_sizes) = even_split(total, max).unwrap();
+            let batches = even_split_iter(total, max).unwrap();
+            assert_eq!(batches.len(), num_batches);
+            assert_eq!(batches.collect::<Vec<_>>(), batch_sizes);
+        }
+    }
+
+    #[test]
+    fn test_even_split_iter_both_ends() {
+        let mut batches = even_split_iter(20, 5).unwrap();
+        assert_eq!(batches.next_back().map(NonZeroUsize::get), Some(5));
+        assert_eq!(batches.next().map(NonZeroUsize::get), Some(5));
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches.rev().count(), 2);
+    }
+
+    #[test]
+    fn test_even_split_iter_errors() {
+        assert_eq!(even_split_iter(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(even_split_iter(10, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_into_batches_matches_split_by_count_clamped() {
+        for len in 1..40 {
+            for num_batches in 1..50 {
+                let batches: Vec<Vec<usize>> = (0..len).into_batches(num_batches).collect();
+                let lengths: Vec<usize> = batches.iter().map(Vec::len).collect();
+                let expected: Vec<usize> = split_by_count_clamped(len, num_batches).unwrap().iter().map(|b| b.get()).collect();
+                assert_eq!(lengths, expected);
+                assert_eq!(batches.concat(), (0..len).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_into_batches_len_and_empty() {
+        let mut batches = vec!["a", "b", "c", "d", "e"].into_iter().into_batches(2);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches.next(), Some(vec!["a", "b", "c"]));
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches.next(), Some(vec!["d", "e"]));
+        assert_eq!(batches.next(), None);
+        assert_eq!(core::iter::empty::<u8>().into_batches(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_into_batches_zero_batches() {
+        let _ = (0..5).into_batches(0);
+    }
+}
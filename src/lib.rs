@@ -14,10 +14,20 @@
 //! - Minimum batch size enforcement
 //! - Batch merging and rebalancing
 //!
+//! ## Cargo features
+//!
+//! - `std` (default): implements `std::error::Error` for `BatchError` and enables the functions
+//!   that need the host (`split_across_cores`, `timed_ranges`). Without it the crate is `no_std`
+//!   and only requires `alloc`.
+//! - `serde`: derives `Serialize` and `Deserialize` for `BatchPlan`.
+//! - `rayon`: adds `par_split_offsets`, which yields batch ranges as a parallel iterator. It
+//!   implies `std`.
+//! - `testing`: adds the `invariants` module of checks for use in property tests.
+//!
 //! ## Usage
 //!
 //! ```rust
-//! use batch_maestro::even_split;
+//! use rsbatch_maestro::even_split;
 //!
 //! fn main() {
 //!     match even_split(128, 8) {
@@ -32,14 +42,66 @@
 //!
 //! For more information and examples, please visit the [GitHub repository](https://github.com/aeromilai/batch-maestro).
 
-use std::num::NonZeroUsize;
-use std::cmp;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+mod assign;
+pub mod checked;
+mod config;
+mod dispatch;
+mod error;
+pub mod generic;
+mod grid;
+#[cfg(feature = "testing")]
+pub mod invariants;
+mod iter;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod plan;
+mod random;
+mod ranges;
+mod rebalance;
+mod schedule;
+mod slices;
+mod stats;
+mod stream;
+mod weighted;
+
+pub use assign::{assign_bitsets, assignment_vector, bin_pack, bitset_contains, cut_edges, round_robin_assign, split_exact_balanced, split_keeping_together, split_min_cut, AssignMode, MAX_EXACT_BINS, MAX_EXACT_ITEMS};
+pub use config::{BatchConfig, SplitBuilder, TypedBatchBuilder};
+pub use dispatch::{parse_command, split_identified, SplitResult};
+pub use error::BatchError;
+pub use generic::BatchInt;
+pub use grid::{split_grid, split_grid_zorder, Tile};
+pub use iter::{even_split_iter, BatchExt, BatchIterator, Batches};
+#[cfg(feature = "rayon")]
+pub use parallel::par_split_offsets;
+pub use plan::{even_split_plan, split, split_range_plans, split_with_remainder_plan, verify_split, verify_split_bounded, BatchPlan, SplitStrategy};
+pub use random::split_jittered;
+pub use ranges::{chunk_boundaries, even_split_offsets, keyset_pages, record_offsets, split_by_cost, split_offsets, split_to_match_prefix, split_with_affinity, split_with_fixed_boundaries, UNASSIGNED_WORKER};
+pub use rebalance::{compact_under_utilized, merge_batches, merge_below_min, merge_to_count, order_for_memory_peak, rebalance, rebalance_across_epochs, rebalance_within, resplit_with_hysteresis, split_many_coalesced, trim_to_quota, FairSplitter};
+pub use schedule::{gantt_schedule, reduction_schedule, schedule_with_concurrency, split_rampup};
+#[cfg(feature = "std")]
+pub use schedule::timed_ranges;
+pub use slices::{split_slice, split_slice_mut};
+pub use stats::{describe_split, imbalance, max_minus_min, render_bars, size_histogram};
+pub use stream::{split_pull, AdaptiveSplitter, BatchAccumulator};
+pub use weighted::{split_by_speed, split_draining, split_geometric, split_reliability_biased, split_weighted_2d, split_weighted_allow_empty, split_weighted_annotated, split_weighted_f64, split_weighted_hamilton, split_weighted_into, split_weighted_min, split_weighted_rounded, split_with_caps, Rounding};
 
 /// Splits a total number into even batches.
 ///
 /// This function takes a total number and a maximum batch size, and attempts to divide the total
 /// into as many even batches as possible, without exceeding the maximum batch size.
 ///
+/// [`generic::even_split`] computes the same split for other unsigned integer types.
+///
 /// # Arguments
 ///
 /// * `total` - The total number to be split.
@@ -60,319 +122,1396 @@ use std::cmp;
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::even_split;
+/// use rsbatch_maestro::even_split;
 /// use std::num::NonZeroUsize;
 ///
 /// let (num_batches, batch_sizes) = even_split(50, 8).unwrap();
 /// assert_eq!(num_batches, 10);
 /// assert_eq!(batch_sizes, vec![NonZeroUsize::new(5).unwrap(); 10]);
 /// ```
-
-pub fn even_split(total: usize, max_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>), String> {
+pub fn even_split(total: usize, max_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>), BatchError> {
+    generic::even_split(total, max_batch_size)
+}
+
+/// Splits a total number into even batches, treating degenerate inputs as valid.
+///
+/// This is an infallible `even_split` for call sites where an empty total is legitimate: a
+/// total of zero yields `(0, vec![])`, and a `max_batch_size` of zero is treated as one.
+/// Otherwise the result is exactly that of `even_split`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split, possibly zero.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{even_split, even_split_saturating};
+///
+/// assert_eq!(even_split_saturating(0, 8), (0, vec![]));
+/// assert_eq!(even_split_saturating(50, 8), even_split(50, 8).unwrap());
+/// assert_eq!(even_split_saturating(3, 0).0, 3);
+/// ```
+pub fn even_split_saturating(total: usize, max_batch_size: usize) -> (usize, Vec<NonZeroUsize>) {
+    if total == 0 {
+        return (0, Vec::new());
+    }
+    even_split(total, max_batch_size.max(1)).expect("a positive total and batch size always split")
+}
+
+/// Splits a total number into the fewest balanced batches that respect a maximum batch size.
+///
+/// `even_split` only uses uniform batches, so it searches downward for a divisor of the total
+/// and may produce many small batches: a total of 50 with a maximum of 8 becomes ten batches of
+/// 5. This function instead uses `ceil(total / max_batch_size)` batches, the fewest possible,
+/// and balances them as in `split_by_count`, so sizes differ by at most one and the first
+/// `total % num_batches` batches are the larger ones.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::even_split_ceil;
+///
+/// let (num_batches, batch_sizes) = even_split_ceil(50, 8).unwrap();
+/// assert_eq!(num_batches, 7);
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 7, 7, 7, 7, 7, 7]);
+/// ```
+pub fn even_split_ceil(total: usize, max_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>), BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let num_batches = total.div_ceil(max_batch_size);
+    Ok((num_batches, split_by_count(total, num_batches)?))
+}
+
+/// Returns the number of batches `even_split` would produce, without allocating the sizes.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing the number of batches.
+///
+/// # Errors
+///
+/// Returns the same errors as `even_split`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::count_batches;
+///
+/// assert_eq!(count_batches(50, 8), Ok(10));
+/// assert_eq!(count_batches(13, 4), Ok(13));
+/// ```
+pub fn count_batches(total: usize, max_batch_size: usize) -> Result<usize, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    Ok(total / generic::even_batch_size(total, max_batch_size))
+}
+
+/// Returns the smallest total that `even_split` divides into exactly `num_batches` batches.
+///
+/// `even_split` picks the largest batch size not exceeding `max_batch_size` that divides the
+/// total, so a total of `num_batches` itself only works when it exceeds `max_batch_size` and has
+/// no divisor in `2..=max_batch_size`. This function searches the candidate totals
+/// `num_batches * batch_size` in increasing order, starting from the first that exceeds
+/// `max_batch_size`, and returns the first with no divisor between `batch_size` and
+/// `max_batch_size`, i.e. the first whose `even_split` produces exactly `num_batches` batches.
+///
+/// # Arguments
+///
+/// * `num_batches` - The desired number of batches.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing the smallest matching total.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The number of batches is zero.
+/// * The max_batch_size is zero.
+/// * The candidate totals overflow `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{even_split, smallest_total_for};
+///
+/// let total = smallest_total_for(5, 8).unwrap();
+/// assert_eq!(total, 25);
+/// assert_eq!(even_split(total, 8).unwrap().0, 5);
+/// ```
+pub fn smallest_total_for(num_batches: usize, max_batch_size: usize) -> Result<usize, BatchError> {
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+    if num_batches == 1 {
+        return Ok(1);
+    }
+
+    // Smaller batch sizes give a total within `max_batch_size`, which stays in one batch.
+    let mut batch_size = max_batch_size / num_batches + 1;
+    loop {
+        let total = num_batches
+            .checked_mul(batch_size)
+            .ok_or(BatchError::Overflow)?;
+        // The divisors of `total` above `batch_size` are `total / k` for `k` below
+        // `num_batches`; only those within `max_batch_size` would be picked instead.
+        let smallest_k = total.div_ceil(max_batch_size);
+        match (smallest_k..num_batches).rev().find(|&k| total.is_multiple_of(k)) {
+            None => return Ok(total),
+            // `total / k` is `num_batches / k * batch_size`, which stays within
+            // `max_batch_size` until the batch size passes this bound.
+            Some(k) if num_batches.is_multiple_of(k) => {
+                batch_size = max_batch_size / (num_batches / k) + 1;
+            }
+            Some(_) => batch_size += 1,
+        }
+    }
+}
+
+/// Returns the largest total that `even_split` divides into exactly `num_batches` batches.
+///
+/// This is `num_batches * max_batch_size`: every batch is filled to the cap, and any larger
+/// total would need more batches.
+///
+/// # Arguments
+///
+/// * `num_batches` - The desired number of batches.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing the largest matching total.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The number of batches is zero.
+/// * The max_batch_size is zero.
+/// * The total overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{even_split, largest_total_for};
+///
+/// let total = largest_total_for(5, 8).unwrap();
+/// assert_eq!(total, 40);
+/// assert_eq!(even_split(total, 8).unwrap().0, 5);
+/// ```
+pub fn largest_total_for(num_batches: usize, max_batch_size: usize) -> Result<usize, BatchError> {
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    num_batches
+        .checked_mul(max_batch_size)
+        .ok_or(BatchError::Overflow)
+}
+
+/// Splits the total based on provided weights for each batch.
+///
+/// [`generic::split_weighted`] computes the same split for other unsigned integer types.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A vector of weights for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights vector is empty.
+/// * Any weight is zero.
+/// * The total is too small to give every batch at least one unit.
+/// * The sum of the weights overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_weighted(100, vec![1, 2, 3]).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(17).unwrap(), NonZeroUsize::new(33).unwrap(), NonZeroUsize::new(50).unwrap()]);
+/// ```
+pub fn split_weighted(total: usize, weights: Vec<usize>) -> Result<Vec<NonZeroUsize>, BatchError> {
+    split_weighted_iter(total, weights)
+}
+
+/// Splits a total number based on weights given by any iterable.
+///
+/// This is `split_weighted` for weights that are computed lazily or live in another
+/// collection. The iterator is consumed once; since the sizes depend on the sum of all the
+/// weights, they are collected internally before splitting.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - The weight of each batch, in order.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_weighted`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_iter;
+///
+/// let batch_sizes = split_weighted_iter(100, (1..=3).map(|i| i * 10)).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![17, 33, 50]);
+/// ```
+pub fn split_weighted_iter<I: IntoIterator<Item = usize>>(total: usize, weights: I) -> Result<Vec<NonZeroUsize>, BatchError> {
+    generic::split_weighted(total, weights.into_iter().collect())
+}
+
+/// Generates a range of possible split configurations based on a min and max batch size.
+///
+/// One configuration is produced for every batch count whose batch size (`total / count`)
+/// lies within the bounds, ordered from the largest batch size down.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split. 
+/// * `min_batch_size` - The minimum allowed size for each batch.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of tuples, each representing a possible split configuration:
+/// (number of batches, batch size, remainder)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The min_batch_size is zero.
+/// * The max_batch_size is less than min_batch_size.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_range;
+///
+/// let configurations = split_range(100, 20, 40).unwrap();
+/// assert_eq!(configurations, vec![(3, 33, 1), (4, 25, 0), (5, 20, 0)]);
+/// ```
+pub fn split_range(total: usize, min_batch_size: usize, max_batch_size: usize) -> Result<Vec<(usize, usize, usize)>, BatchError> {
+    Ok(split_range_iter(total, min_batch_size, max_batch_size)?.collect())
+}
+
+/// Lazily generates the split configurations of `split_range`.
+///
+/// The configurations are produced one at a time in the same order as `split_range`, so large
+/// ranges can be searched or truncated with `take` without building the full vector.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batch_size` - The minimum allowed size for each batch.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing an iterator over `(number of batches, batch size, remainder)` tuples.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_range`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_range_iter;
+///
+/// let mut configurations = split_range_iter(1_000_000, 1, 1_000_000).unwrap();
+/// assert_eq!(configurations.next(), Some((1, 1_000_000, 0)));
+/// assert_eq!(configurations.nth(1), Some((3, 333_333, 1)));
+/// ```
+pub fn split_range_iter(total: usize, min_batch_size: usize, max_batch_size: usize) -> Result<impl DoubleEndedIterator<Item = (usize, usize, usize)>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if min_batch_size == 0 {
+        return Err(BatchError::ZeroMinBatchSize);
+    }
+    if max_batch_size < min_batch_size {
+        return Err(BatchError::MinExceedsMax { min: min_batch_size, max: max_batch_size });
+    }
+
+    Ok((total.div_ceil(max_batch_size)..=total / min_batch_size).map(move |num_batches| (num_batches, total / num_batches, total % num_batches)))
+}
+
+/// Generates at most `limit` split configurations of `split_range`.
+///
+/// The configurations are the first `limit` that `split_range` would return, in the same
+/// order, without enumerating the rest of the range.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batch_size` - The minimum allowed size for each batch.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `limit` - The largest number of configurations to return.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of at most `limit` `(number of batches, batch size, remainder)`
+/// tuples.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_range`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_range_limited;
+///
+/// let configurations = split_range_limited(100, 20, 40, 2).unwrap();
+/// assert_eq!(configurations, vec![(3, 33, 1), (4, 25, 0)]);
+/// ```
+pub fn split_range_limited(total: usize, min_batch_size: usize, max_batch_size: usize, limit: usize) -> Result<Vec<(usize, usize, usize)>, BatchError> {
+    Ok(split_range_iter(total, min_batch_size, max_batch_size)?.take(limit).collect())
+}
+
+/// Expands a configuration produced by `split_range` into its batch sizes.
+///
+/// The remainder is spread over the batches rather than left over: the first `remainder`
+/// batches get one extra unit, as in `split_by_count`, so the sizes add up to
+/// `num_batches * batch_size + remainder`.
+///
+/// # Arguments
+///
+/// * `config` - A `(number of batches, batch size, remainder)` tuple.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The number of batches is zero.
+/// * The configuration covers a total of zero, or a total smaller than the number of batches.
+/// * The covered total overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{expand_range_config, split_range};
+///
+/// let configurations = split_range(100, 20, 40).unwrap();
+/// let batch_sizes = expand_range_config(configurations[0]).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![34, 33, 33]);
+/// ```
+pub fn expand_range_config(config: (usize, usize, usize)) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let (num_batches, batch_size, remainder) = config;
+    let total = num_batches
+        .checked_mul(batch_size)
+        .and_then(|covered| covered.checked_add(remainder))
+        .ok_or(BatchError::Overflow)?;
+    split_by_count(total, num_batches)
+}
+
+/// Finds the most even split possible within a given range of batch counts.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batches` - The minimum number of batches.
+/// * `max_batches` - The maximum number of batches.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The min_batches is zero.
+/// * The max_batches is less than min_batches.
+/// * The total is smaller than min_batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::optimize_split;
+/// use std::num::NonZeroUsize;
+///
+/// let (num_batches, batch_sizes) = optimize_split(100, 3, 5).unwrap();
+/// assert_eq!(num_batches, 4);
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(25).unwrap(); 4]);
+/// ```
+pub fn optimize_split(total: usize, min_batches: usize, max_batches: usize) -> Result<(usize, Vec<NonZeroUsize>), BatchError> {
+    optimize_split_with(total, min_batches, max_batches, CountPreference::Fewest)
+}
+
+/// Finds the most even split within a range of batch counts, also reporting its remainder.
+///
+/// The count and batch sizes are exactly those of `optimize_split`, which spreads the remainder
+/// over the leading batches. The third element is that remainder, `total % num_batches`: the
+/// smallest one found in the range, and zero exactly when some count in the range divides the
+/// total. A nonzero remainder suggests widening the range to find an exact split.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batches` - The minimum number of batches.
+/// * `max_batches` - The maximum number of batches.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+/// 3. The remainder that no count in the range could eliminate.
+///
+/// # Errors
+///
+/// Returns the same errors as `optimize_split`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::optimize_split_report;
+///
+/// let (num_batches, _, remainder) = optimize_split_report(100, 3, 5).unwrap();
+/// assert_eq!((num_batches, remainder), (4, 0));
+/// let (num_batches, batch_sizes, remainder) = optimize_split_report(101, 3, 5).unwrap();
+/// assert_eq!((num_batches, remainder), (4, 1));
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![26, 25, 25, 25]);
+/// ```
+pub fn optimize_split_report(total: usize, min_batches: usize, max_batches: usize) -> Result<(usize, Vec<NonZeroUsize>, usize), BatchError> {
+    let (num_batches, batch_sizes) = optimize_split(total, min_batches, max_batches)?;
+    Ok((num_batches, batch_sizes, total % num_batches))
+}
+
+/// Which of several equally even batch counts [`optimize_split_with`] picks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CountPreference {
+    /// The smallest count, giving the largest batches, as in [`optimize_split`].
+    #[default]
+    Fewest,
+    /// The largest count, giving the smallest batches.
+    Most,
+    /// The median of the tied counts, taking the lower one when their number is even.
+    BalancedSize,
+}
+
+/// Finds the most even split possible within a given range of batch counts, choosing among
+/// equally even counts according to a [`CountPreference`].
+///
+/// A count is as even as another when it leaves the same remainder; the smallest remainder in
+/// the range wins. Counts larger than the total are never considered, since they would produce
+/// empty batches.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batches` - The minimum number of batches.
+/// * `max_batches` - The maximum number of batches.
+/// * `preference` - Which count to pick when several leave the smallest remainder.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The min_batches is zero.
+/// * The max_batches is less than min_batches.
+/// * The total is smaller than min_batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{optimize_split_with, CountPreference};
+///
+/// assert_eq!(optimize_split_with(100, 2, 10, CountPreference::Fewest).unwrap().0, 2);
+/// assert_eq!(optimize_split_with(100, 2, 10, CountPreference::Most).unwrap().0, 10);
+/// assert_eq!(optimize_split_with(100, 2, 10, CountPreference::BalancedSize).unwrap().0, 4);
+/// ```
+pub fn optimize_split_with(total: usize, min_batches: usize, max_batches: usize, preference: CountPreference) -> Result<(usize, Vec<NonZeroUsize>), BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if min_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if max_batches < min_batches {
+        return Err(BatchError::MinBatchesExceedMax { min: min_batches, max: max_batches });
+    }
+    if total < min_batches {
+        return Err(BatchError::InsufficientTotal { total, min_required: min_batches });
+    }
+
+    let counts = min_batches..=max_batches.min(total);
+    let mut min_remainder = total;
+    let mut fewest = min_batches;
+    for num_batches in counts.clone() {
+        let remainder = total % num_batches;
+        if remainder < min_remainder {
+            min_remainder = remainder;
+            fewest = num_batches;
+        }
+        if remainder == 0 {
+            break;
+        }
+    }
+
+    let mut tied = counts.filter(|num_batches| total % num_batches == min_remainder);
+    let num_batches = match preference {
+        CountPreference::Fewest => fewest,
+        CountPreference::Most => tied.next_back().unwrap_or(fewest),
+        CountPreference::BalancedSize => {
+            let num_tied = tied.clone().count();
+            tied.nth((num_tied - 1) / 2).unwrap_or(fewest)
+        }
+    };
+
+    Ok((num_batches, split_by_count(total, num_batches)?))
+}
+
+/// Splits a total number into even batches, ensuring each batch meets a minimum size requirement.
+///
+/// Every batch lies within `[min_batch_size, max_batch_size]`; the batches are those of
+/// `split_with_min_and_max`. A `min_batch_size` of zero places no lower bound on the sizes.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `min_batch_size` - The minimum required size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The number of batches.
+/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * The min_batch_size is greater than max_batch_size.
+/// * No number of batches keeps every size within `[min_batch_size, max_batch_size]`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_min_batch;
+/// use std::num::NonZeroUsize;
+///
+/// let (num_batches, batch_sizes) = split_with_min_batch(100, 30, 20).unwrap();
+/// assert_eq!(num_batches, 4);
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(25).unwrap(); 4]);
+/// ```
+pub fn split_with_min_batch(total: usize, max_batch_size: usize, min_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>), BatchError> {
+    let batch_sizes = split_with_min_and_max(total, min_batch_size.max(1), max_batch_size)?;
+    Ok((batch_sizes.len(), batch_sizes))
+}
+
+/// Splits a total number into balanced batches whose sizes all lie within `[min, max]`.
+///
+/// The fewest batches that keep every size at or below `max_batch_size` are used, which is also
+/// the count that makes reaching `min_batch_size` easiest. The batches are balanced as in
+/// `split_by_count`, so sizes differ by at most one.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `min_batch_size` - The minimum size of each batch, inclusive.
+/// * `max_batch_size` - The maximum size of each batch, inclusive.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The min_batch_size or max_batch_size is zero.
+/// * The min_batch_size is greater than max_batch_size.
+/// * No number of batches keeps every size within the bounds, such as a total of 7 with bounds
+///   of 4 and 5.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_min_and_max;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_with_min_and_max(23, 4, 5).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![5, 5, 5, 4, 4]);
+/// assert!(split_with_min_and_max(7, 4, 5).is_err());
+/// ```
+pub fn split_with_min_and_max(total: usize, min_batch_size: usize, max_batch_size: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if min_batch_size == 0 {
+        return Err(BatchError::ZeroMinBatchSize);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+    if min_batch_size > max_batch_size {
+        return Err(BatchError::MinExceedsMax { min: min_batch_size, max: max_batch_size });
+    }
+
+    let num_batches = total.div_ceil(max_batch_size);
+    if num_batches.checked_mul(min_batch_size).is_none_or(|required| required > total) {
+        return Err(BatchError::ImpossibleConstraint(format!(
+            "No number of batches splits {} into sizes between {} and {}",
+            total, min_batch_size, max_batch_size
+        )));
+    }
+
+    split_by_count(total, num_batches)
+}
+
+/// Splits a total number into balanced batches whose sizes are collectively closest to a target.
+///
+/// The number of batches minimizes the sum of `|size - target|` over the batches, and the sizes
+/// are then balanced as in `split_by_count`. Below `total / target` batches every size is at
+/// least the target and the deviation shrinks as batches are added; above it every size is at
+/// most the target and the deviation grows, so only the two counts around `total / target` need
+/// to be compared. When both give the same deviation, the fewer batches are chosen.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `target` - The preferred size of each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The target is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_near_target;
+///
+/// let batch_sizes = split_near_target(100, 30).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![34, 33, 33]);
+/// ```
+pub fn split_near_target(total: usize, target: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if target == 0 {
+        return Err(BatchError::InvalidInput(String::from("Target batch size must be a positive number")));
+    }
+
+    let deviation = |num_batches: usize| {
+        let base_size = total / num_batches;
+        let remainder = total % num_batches;
+        remainder as u128 * (base_size + 1).abs_diff(target) as u128 + (num_batches - remainder) as u128 * base_size.abs_diff(target) as u128
+    };
+    let fewer = (total / target).max(1);
+    let more = total.div_ceil(target);
+    let num_batches = if deviation(more) < deviation(fewer) { more } else { fewer };
+
+    split_by_count(total, num_batches)
+}
+
+/// Splits a total number into a specified number of batches.
+///
+/// This function divides the total into the given number of batches,
+/// allowing for uneven distribution if necessary.
+///
+/// [`generic::split_by_count`] computes the same split for other unsigned integer types.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to split the total into.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_count;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_by_count(10, 3).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// ```
+pub fn split_by_count(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    generic::split_by_count(total, num_batches)
+}
+
+/// Splits a total number into at most a specified number of batches.
+///
+/// This behaves like `split_by_count`, except that when the total is smaller than the number of
+/// batches the count is reduced to the total instead of failing. The returned vector therefore
+/// has `min(total, num_batches)` entries, which may be fewer than requested.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The largest number of batches to produce.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_count_clamped;
+/// use std::num::NonZeroUsize;
+///
+/// assert_eq!(split_by_count_clamped(3, 10).unwrap(), vec![NonZeroUsize::new(1).unwrap(); 3]);
+/// assert_eq!(split_by_count_clamped(10, 3).unwrap().len(), 3);
+/// ```
+pub fn split_by_count_clamped(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    split_by_count(total, num_batches.min(total))
+}
+
+/// Splits a total number into a specified number of batches, pairing each size with its index.
+///
+/// The sizes are those of `split_by_count`, and the indices run from 0 to `num_batches - 1`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to create.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(index, size)` pairs in batch order.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_by_count`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::enumerate_split;
+/// use std::num::NonZeroUsize;
+///
+/// let batches = enumerate_split(10, 3).unwrap();
+/// assert_eq!(batches, vec![(0, NonZeroUsize::new(4).unwrap()), (1, NonZeroUsize::new(3).unwrap()), (2, NonZeroUsize::new(3).unwrap())]);
+/// ```
+pub fn enumerate_split(total: usize, num_batches: usize) -> Result<Vec<(usize, NonZeroUsize)>, BatchError> {
+    Ok(split_by_count(total, num_batches)?.into_iter().enumerate().collect())
+}
+
+/// Splits a total number into a compile-time number of batches, returned as an array.
+///
+/// The sizes are exactly those of `split_by_count(total, N)`, with the first `total % N`
+/// batches one larger, but nothing is allocated on the heap.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+///
+/// # Returns
+///
+/// A `Result` containing an array of `N` batch sizes.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * `N` is zero.
+/// * The total is smaller than `N`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_count_array;
+///
+/// let batch_sizes = split_by_count_array::<3>(10).unwrap();
+/// assert_eq!(batch_sizes.map(|b| b.get()), [4, 3, 3]);
+/// assert!(split_by_count_array::<3>(2).is_err());
+/// ```
+pub fn split_by_count_array<const N: usize>(total: usize) -> Result<[NonZeroUsize; N], BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if N == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    let base_size = NonZeroUsize::new(total / N).ok_or(BatchError::InsufficientTotal { total, min_required: N })?;
+    let remainder = total % N;
+
+    Ok(core::array::from_fn(|i| if i < remainder { base_size.saturating_add(1) } else { base_size }))
+}
+
+/// Where [`split_by_count_with`] places the batches that carry one extra unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RemainderStrategy {
+    /// The larger batches come first, as in [`split_by_count`].
+    #[default]
+    Front,
+    /// The larger batches come last.
+    Back,
+    /// The larger batches are spaced as evenly as possible across the sequence.
+    Spread,
+}
+
+/// Splits a total number into a specified number of batches, placing the larger batches
+/// according to a [`RemainderStrategy`].
+///
+/// The sizes are the same multiset as `split_by_count`: `total % num_batches` batches hold one
+/// unit more than the others. With `Spread`, the `k`-th larger batch is placed at index
+/// `(2k + 1) * num_batches / (2 * remainder)`, the centre of the `k`-th of `remainder` equal
+/// segments of the sequence.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to split the total into.
+/// * `strategy` - Where to place the larger batches.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_by_count`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_by_count_with, RemainderStrategy};
+///
+/// let sizes = |strategy| split_by_count_with(10, 3, strategy).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+/// assert_eq!(sizes(RemainderStrategy::Front), vec![4, 3, 3]);
+/// assert_eq!(sizes(RemainderStrategy::Back), vec![3, 3, 4]);
+/// assert_eq!(sizes(RemainderStrategy::Spread), vec![3, 4, 3]);
+/// ```
+pub fn split_by_count_with(total: usize, num_batches: usize, strategy: RemainderStrategy) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let mut batch_sizes = split_by_count(total, num_batches)?;
+    let remainder = total % num_batches;
+
+    match strategy {
+        RemainderStrategy::Front => {}
+        RemainderStrategy::Back => batch_sizes.reverse(),
+        RemainderStrategy::Spread => {
+            let base_size = batch_sizes[num_batches - 1];
+            batch_sizes.fill(base_size);
+            for k in 0..remainder {
+                let index = ((2 * k + 1) as u128 * num_batches as u128 / (2 * remainder) as u128) as usize;
+                batch_sizes[index] = base_size.saturating_add(1);
+            }
+        }
+    }
+
+    Ok(batch_sizes)
+}
+
+/// Splits a total number into a specified number of batches, sorted by size.
+///
+/// The sizes are the same multiset as `split_by_count`. With `descending` they come largest
+/// first, exactly as `split_by_count` returns them; otherwise they come smallest first, so that
+/// the smaller batches are dispatched before the larger ones. This is `split_by_count_with`
+/// using `RemainderStrategy::Front` or `RemainderStrategy::Back`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to split the total into.
+/// * `descending` - Whether the larger batches come first.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_by_count`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_count_sorted;
+///
+/// let sizes = |descending| split_by_count_sorted(10, 3, descending).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+/// assert_eq!(sizes(false), vec![3, 3, 4]);
+/// assert_eq!(sizes(true), vec![4, 3, 3]);
+/// ```
+pub fn split_by_count_sorted(total: usize, num_batches: usize, descending: bool) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let strategy = if descending { RemainderStrategy::Front } else { RemainderStrategy::Back };
+    split_by_count_with(total, num_batches, strategy)
+}
+
+/// Splits a total number into a specified number of batches, alternating the larger batches
+/// between the front and the back.
+///
+/// The sizes are the same multiset as `split_by_count`, but the `total % num_batches` batches
+/// that carry one extra unit are placed alternately from both ends: the first goes to index
+/// `0`, the second to index `num_batches - 1`, the third to index `1`, the fourth to index
+/// `num_batches - 2`, and so on. This balances the load seen by consumers working inward from
+/// both ends at once.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to split the total into.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns the same errors as `split_by_count`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_count_alternating;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_by_count_alternating(11, 4).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(2).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// ```
+pub fn split_by_count_alternating(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let balanced = split_by_count(total, num_batches)?;
+    let base_size = balanced[num_batches - 1];
+    let remainder = total % num_batches;
+
+    let mut batch_sizes = vec![base_size; num_batches];
+    for i in 0..remainder {
+        let index = if i % 2 == 0 { i / 2 } else { num_batches - 1 - i / 2 };
+        batch_sizes[index] = base_size.saturating_add(1);
+    }
+
+    Ok(batch_sizes)
+}
+
+/// Splits a total number into one balanced batch per available CPU core.
+///
+/// The number of batches is taken from `std::thread::available_parallelism()`, falling back to
+/// a single batch when the parallelism of the host cannot be determined. The sizes are computed
+/// with `split_by_count`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The total is smaller than the number of available cores.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_across_cores;
+///
+/// let batch_sizes = split_across_cores(1024).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 1024);
+/// ```
+#[cfg(feature = "std")]
+pub fn split_across_cores(total: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let cores = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    split_by_count(total, cores)
+}
+
+/// Splits a total number into a primary plan and a reduced-capacity fallback plan.
+///
+/// Both plans are balanced splits of the same total computed with `split_by_count`: one with a
+/// batch per primary worker and one with a batch per fallback worker. Having both precomputed
+/// lets a scheduler switch to the fallback plan immediately when it enters degraded mode.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `primary_workers` - The number of workers available in normal operation.
+/// * `fallback_workers` - The number of workers available in degraded mode.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The batch sizes for the primary workers.
+/// 2. The batch sizes for the fallback workers.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * Either worker count is zero.
+/// * Either worker count exceeds the total.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_fallback;
+///
+/// let (primary, fallback) = split_with_fallback(100, 8, 3).unwrap();
+/// assert_eq!(primary.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![13, 13, 13, 13, 12, 12, 12, 12]);
+/// assert_eq!(fallback.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![34, 33, 33]);
+/// ```
+pub fn split_with_fallback(total: usize, primary_workers: usize, fallback_workers: usize) -> Result<(Vec<NonZeroUsize>, Vec<NonZeroUsize>), BatchError> {
     if total == 0 {
-        return Err(String::from("Total must be a positive number"));
-    }
-    if max_batch_size == 0 {
-        return Err(String::from("Max batch size must be a positive number"));
+        return Err(BatchError::ZeroTotal);
     }
-
-    if total <= max_batch_size {
-        return Ok((1, vec![NonZeroUsize::new(total).unwrap()]));
+    if primary_workers == 0 || fallback_workers == 0 {
+        return Err(BatchError::ZeroBatchCount);
     }
-
-    let mut batch_size = max_batch_size;
-    while batch_size > 1 {
-        if total % batch_size == 0 {
-            let num_batches = total / batch_size;
-            return Ok((num_batches, vec![NonZeroUsize::new(batch_size).unwrap(); num_batches]));
-        }
-        batch_size -= 1;
+    if primary_workers > total || fallback_workers > total {
+        return Err(BatchError::InsufficientTotal { total, min_required: primary_workers.max(fallback_workers) });
     }
 
-    Ok((total, vec![NonZeroUsize::new(1).unwrap(); total]))
+    Ok((split_by_count(total, primary_workers)?, split_by_count(total, fallback_workers)?))
 }
 
-/// Splits the total based on provided weights for each batch.
+/// Covers a total with batches drawn from a small set of allowed sizes.
+///
+/// Like making change, the largest allowed size is used as many times as it fits, then the next
+/// largest, and so on. When each allowed size is a multiple of the next smaller one, such as
+/// powers of two, this uses the fewest batches possible. Each size that is used at least once is
+/// reported with its count, largest first. Including 1 in `allowed_sizes` makes every total
+/// representable; without it, totals that leave a remainder smaller than the smallest size are
+/// rejected.
 ///
 /// # Arguments
 ///
 /// * `total` - The total number to be split.
-/// * `weights` - A vector of weights for each batch.
+/// * `allowed_sizes` - The allowed batch sizes, in strictly ascending order.
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+/// A `Result` containing `(size, count)` pairs in descending order of size.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * The total is zero.
-/// * The weights vector is empty.
-/// * Any weight is zero.
+/// * The allowed_sizes slice is empty, contains zero or is not strictly ascending.
+/// * The total cannot be covered exactly by the allowed sizes.
 ///
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::split_weighted;
-/// use std::num::NonZeroUsize;
+/// use rsbatch_maestro::mixed_radix_split;
 ///
-/// let batch_sizes = split_weighted(100, vec![1, 2, 3]).unwrap();
-/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(17).unwrap(), NonZeroUsize::new(33).unwrap(), NonZeroUsize::new(50).unwrap()]);
+/// let groups = mixed_radix_split(100, &[1, 8, 32]).unwrap();
+/// assert_eq!(groups, vec![(32, 3), (1, 4)]);
 /// ```
-pub fn split_weighted(total: usize, weights: Vec<usize>) -> Result<Vec<NonZeroUsize>, String> {
+pub fn mixed_radix_split(total: usize, allowed_sizes: &[usize]) -> Result<Vec<(usize, usize)>, BatchError> {
     if total == 0 {
-        return Err(String::from("Total must be a positive number"));
+        return Err(BatchError::ZeroTotal);
     }
-    if weights.is_empty() {
-        return Err(String::from("Weights vector must not be empty"));
+    if allowed_sizes.is_empty() || allowed_sizes[0] == 0 {
+        return Err(BatchError::InvalidInput(String::from("Allowed sizes must be non-empty and positive")));
     }
-    if weights.iter().any(|&w| w == 0) {
-        return Err(String::from("All weights must be positive numbers"));
+    if allowed_sizes.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(BatchError::InvalidInput(String::from("Allowed sizes must be strictly ascending")));
     }
 
-    let weight_sum: usize = weights.iter().sum();
-    let mut batches = Vec::with_capacity(weights.len());
     let mut remaining = total;
-
-    for (i, &weight) in weights.iter().enumerate() {
-        let size = if i == weights.len() - 1 {
-            remaining
-        } else {
-            (total * weight) / weight_sum
-        };
-        batches.push(NonZeroUsize::new(size).unwrap());
-        remaining -= size;
+    let mut groups = Vec::new();
+    for &size in allowed_sizes.iter().rev() {
+        let count = remaining / size;
+        if count > 0 {
+            groups.push((size, count));
+            remaining -= size * count;
+        }
+    }
+    if remaining > 0 {
+        return Err(BatchError::ImpossibleConstraint(format!("A remainder of {} cannot be covered by the allowed sizes", remaining)));
     }
 
-    Ok(batches)
+    Ok(groups)
 }
 
-/// Generates a range of possible split configurations based on a min and max batch size.
+/// Splits a total number into batches whose sizes are all powers of two.
+///
+/// The primary batch size is the largest power of two not exceeding `max_batch_size`, and as
+/// many full batches of it as fit come first. The tail is not a single remainder batch but a
+/// cascade: the remainder is broken into its binary decomposition, one batch per set bit, in
+/// decreasing order. Every batch is therefore a power of two, and there are at most
+/// `log2(primary size)` tail batches.
 ///
 /// # Arguments
 ///
-/// * `total` - The total number to be split. 
-/// * `min_batch_size` - The minimum allowed size for each batch.
+/// * `total` - The total number to be split.
 /// * `max_batch_size` - The maximum allowed size for each batch.
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of tuples, each representing a possible split configuration:
-/// (number of batches, batch size, remainder)
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * The total is zero.
-/// * The min_batch_size is zero.
-/// * The max_batch_size is less than min_batch_size.
+/// * The max_batch_size is zero.
 ///
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::split_range;
+/// use rsbatch_maestro::split_pow2;
 ///
-/// let configurations = split_range(100, 20, 40).unwrap();
-/// assert_eq!(configurations, vec![(3, 33, 1), (4, 25, 0), (5, 20, 0)]);
+/// let batch_sizes = split_pow2(50, 8).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 8, 8, 8, 8, 8, 2]);
+/// let batch_sizes = split_pow2(23, 12).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 8, 4, 2, 1]);
 /// ```
-pub fn split_range(total: usize, min_batch_size: usize, max_batch_size: usize) -> Result<Vec<(usize, usize, usize)>, String> {
+pub fn split_pow2(total: usize, max_batch_size: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
     if total == 0 {
-        return Err(String::from("Total must be a positive number"));
-    }
-    if min_batch_size == 0 {
-        return Err(String::from("Minimum batch size must be a positive number"));
+        return Err(BatchError::ZeroTotal);
     }
-    if max_batch_size < min_batch_size {
-        return Err(String::from("Maximum batch size must be greater than or equal to minimum batch size"));
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
     }
 
-    let mut configurations = Vec::new();
-    for batch_size in (min_batch_size..=max_batch_size).rev() {
-        let num_batches = total / batch_size;
-        let remainder = total % batch_size;
-        if num_batches > 0 {
-            configurations.push((num_batches, batch_size, remainder));
+    let primary = 1usize << max_batch_size.ilog2();
+    let remainder = total % primary;
+    let mut batch_sizes = vec![NonZeroUsize::new(primary).unwrap(); total / primary];
+    for bit in (0..primary.trailing_zeros()).rev() {
+        if remainder & (1 << bit) != 0 {
+            batch_sizes.push(NonZeroUsize::new(1 << bit).unwrap());
         }
     }
 
-    Ok(configurations)
+    Ok(batch_sizes)
 }
 
-/// Finds the most even split possible within a given range of batch counts.
+/// Splits a total number into batches whose sizes are multiples of an alignment.
+///
+/// The primary batch size is the largest multiple of `alignment` not exceeding
+/// `max_batch_size`, and as many full batches of it as fit come first. What is left is split
+/// into at most two more batches: the largest multiple of `alignment` it contains, then the
+/// unaligned tail. Every batch except possibly the last is therefore a multiple of `alignment`,
+/// and the split uses the fewest batches possible under that rule: a total of 100 with a maximum
+/// of 64 and an alignment of 32 becomes `[64, 32, 4]`, not `[32, 32, 32, 4]`.
 ///
 /// # Arguments
 ///
 /// * `total` - The total number to be split.
-/// * `min_batches` - The minimum number of batches.
-/// * `max_batches` - The maximum number of batches.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `alignment` - The multiple every batch but the last must be, such as a warp size.
 ///
 /// # Returns
 ///
-/// A `Result` containing a tuple with:
-/// 1. The number of batches.
-/// 2. A vector of `NonZeroUsize` representing the size of each batch.
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * The total is zero.
-/// * The min_batches is zero.
-/// * The max_batches is less than min_batches.
+/// * The max_batch_size is zero.
+/// * The alignment is zero or larger than the max_batch_size.
 ///
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::optimize_split;
-/// use std::num::NonZeroUsize;
+/// use rsbatch_maestro::split_aligned;
 ///
-/// let (num_batches, batch_sizes) = optimize_split(100, 3, 5).unwrap();
-/// assert_eq!(num_batches, 4);
-/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(25).unwrap(); 4]);
+/// let batch_sizes = split_aligned(100, 64, 32).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![64, 32, 4]);
+/// let batch_sizes = split_aligned(100, 50, 32).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![32, 32, 32, 4]);
 /// ```
-pub fn optimize_split(total: usize, min_batches: usize, max_batches: usize) -> Result<(usize, Vec<NonZeroUsize>), String> {
+pub fn split_aligned(total: usize, max_batch_size: usize, alignment: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
     if total == 0 {
-        return Err(String::from("Total must be a positive number"));
+        return Err(BatchError::ZeroTotal);
     }
-    if min_batches == 0 {
-        return Err(String::from("Minimum number of batches must be a positive number"));
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
     }
-    if max_batches < min_batches {
-        return Err(String::from("Maximum number of batches must be greater than or equal to minimum number of batches"));
+    if alignment == 0 {
+        return Err(BatchError::InvalidInput(String::from("Alignment must be a positive number")));
     }
-
-    let mut best_num_batches = min_batches;
-    let mut min_remainder = total;
-
-    for num_batches in min_batches..=max_batches {
-        let remainder = total % num_batches;
-        if remainder < min_remainder {
-            best_num_batches = num_batches;
-            min_remainder = remainder;
-        }
-        if remainder == 0 {
-            break;
-        }
+    if alignment > max_batch_size {
+        return Err(BatchError::InvalidInput(format!("Alignment {} must not exceed the max batch size {}", alignment, max_batch_size)));
     }
 
-    let base_size = total / best_num_batches;
-    let mut batch_sizes = vec![NonZeroUsize::new(base_size).unwrap(); best_num_batches];
-    for i in 0..min_remainder {
-        batch_sizes[i] = NonZeroUsize::new(base_size + 1).unwrap();
-    }
+    let primary = max_batch_size - max_batch_size % alignment;
+    let remainder = total % primary;
+    let mut batch_sizes = vec![NonZeroUsize::new(primary).unwrap(); total / primary];
+    batch_sizes.extend(NonZeroUsize::new(remainder - remainder % alignment));
+    batch_sizes.extend(NonZeroUsize::new(remainder % alignment));
 
-    Ok((best_num_batches, batch_sizes))
+    Ok(batch_sizes)
 }
 
-/// Splits a total number into even batches, ensuring each batch meets a minimum size requirement.
+/// Splits a total number into even batches, returning the remainder separately.
+///
+/// This function is similar to `even_split`, but instead of including the remainder
+/// in the last batch, it returns it as a separate value.
+///
+/// [`generic::split_with_remainder`] computes the same split for other unsigned integer types.
 ///
 /// # Arguments
 ///
 /// * `total` - The total number to be split.
 /// * `max_batch_size` - The maximum allowed size for each batch.
-/// * `min_batch_size` - The minimum required size for each batch.
 ///
 /// # Returns
 ///
 /// A `Result` containing a tuple with:
 /// 1. The number of batches.
 /// 2. A vector of `NonZeroUsize` representing the size of each batch.
+/// 3. The remainder.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * The total is zero.
 /// * The max_batch_size is zero.
-/// * The min_batch_size is greater than max_batch_size.
-/// * It's impossible to create batches that meet the minimum size requirement.
 ///
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::split_with_min_batch;
+/// use rsbatch_maestro::split_with_remainder;
 /// use std::num::NonZeroUsize;
 ///
-/// let (num_batches, batch_sizes) = split_with_min_batch(100, 30, 20).unwrap();
-/// assert_eq!(num_batches, 4);
-/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(25).unwrap(); 4]);
+/// let (num_batches, batch_sizes, remainder) = split_with_remainder(50, 8).unwrap();
+/// assert_eq!(num_batches, 6);
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(8).unwrap(); 6]);
+/// assert_eq!(remainder, 2);
 /// ```
-pub fn split_with_min_batch(total: usize, max_batch_size: usize, min_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>), String> {
-    if total == 0 {
-        return Err(String::from("Total must be a positive number"));
-    }
-    if max_batch_size == 0 {
-        return Err(String::from("Max batch size must be a positive number"));
-    }
-    if min_batch_size > max_batch_size {
-        return Err(String::from("Min batch size must be less than or equal to max batch size"));
-    }
-
-    let num_batches = (total + min_batch_size - 1) / min_batch_size;
-    let base_size = total / num_batches;
-    let remainder = total % num_batches;
-
-    let mut batch_sizes = Vec::with_capacity(num_batches);
-    for i in 0..num_batches {
-        let size = base_size + if i < remainder { 1 } else { 0 };
-        batch_sizes.push(NonZeroUsize::new(size).unwrap());
-    }
-
-    Ok((num_batches, batch_sizes))
+pub fn split_with_remainder(total: usize, max_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>, usize), BatchError> {
+    generic::split_with_remainder(total, max_batch_size)
 }
 
-
-/// Splits a total number into a specified number of batches.
+/// Splits a total number into full batches, returning any leftover as an optional batch.
 ///
-/// This function divides the total into the given number of batches,
-/// allowing for uneven distribution if necessary.
+/// This is `split_with_remainder` with the remainder expressed as `Option<NonZeroUsize>`
+/// instead of a `usize` where zero means "none", so checking for leftover items becomes a
+/// plain `if let Some(..)`.
 ///
 /// # Arguments
 ///
 /// * `total` - The total number to be split.
-/// * `num_batches` - The number of batches to split the total into.
+/// * `max_batch_size` - The maximum allowed size for each batch.
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+/// A `Result` containing a tuple with:
+/// 1. A vector of `NonZeroUsize` representing the size of each batch.
+/// 2. The remainder, or `None` when the total divides evenly.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// * The total is zero.
-/// * The number of batches is zero.
+/// * The max_batch_size is zero.
 ///
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::split_by_count;
+/// use rsbatch_maestro::split_with_optional_remainder;
 /// use std::num::NonZeroUsize;
 ///
-/// let batch_sizes = split_by_count(10, 3).unwrap();
-/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// let (batch_sizes, remainder) = split_with_optional_remainder(50, 8).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(8).unwrap(); 6]);
+/// assert_eq!(remainder, NonZeroUsize::new(2));
 /// ```
-pub fn split_by_count(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsize>, String> {
-    if total == 0 {
-        return Err(String::from("Total must be a positive number"));
-    }
-    if num_batches == 0 {
-        return Err(String::from("Number of batches must be a positive number"));
-    }
-
-    let base_size = total / num_batches;
-    let remainder = total % num_batches;
-
-    let mut batches = Vec::with_capacity(num_batches);
-    for i in 0..num_batches {
-        let size = base_size + if i < remainder { 1 } else { 0 };
-        batches.push(NonZeroUsize::new(size).ok_or_else(|| String::from("Failed to create NonZeroUsize"))?);
-    }
-
-    Ok(batches)
+pub fn split_with_optional_remainder(total: usize, max_batch_size: usize) -> Result<(Vec<NonZeroUsize>, Option<NonZeroUsize>), BatchError> {
+    let (_, batch_sizes, remainder) = split_with_remainder(total, max_batch_size)?;
+    Ok((batch_sizes, NonZeroUsize::new(remainder)))
 }
 
-/// Splits a total number into even batches, returning the remainder separately.
+/// Splits a total number into full batches followed by a final batch holding the remainder.
 ///
-/// This function is similar to `even_split`, but instead of including the remainder
-/// in the last batch, it returns it as a separate value.
+/// This is `split_with_remainder` with the remainder appended to the batch list when it is not
+/// zero, so the result is a complete split whose sizes add up to the total. Every batch is at
+/// most `max_batch_size`, and only the last one may be smaller.
 ///
 /// # Arguments
 ///
@@ -381,10 +1520,7 @@ pub fn split_by_count(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsi
 ///
 /// # Returns
 ///
-/// A `Result` containing a tuple with:
-/// 1. The number of batches.
-/// 2. A vector of `NonZeroUsize` representing the size of each batch.
-/// 3. The remainder.
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
 ///
 /// # Errors
 ///
@@ -395,34 +1531,15 @@ pub fn split_by_count(total: usize, num_batches: usize) -> Result<Vec<NonZeroUsi
 /// # Examples
 ///
 /// ```
-/// use batch_maestro::split_with_remainder;
-/// use std::num::NonZeroUsize;
+/// use rsbatch_maestro::split_with_remainder_batched;
 ///
-/// let (num_batches, batch_sizes, remainder) = split_with_remainder(50, 8).unwrap();
-/// assert_eq!(num_batches, 6);
-/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(8).unwrap(); 6]);
-/// assert_eq!(remainder, 2);
+/// let batch_sizes = split_with_remainder_batched(50, 8).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 8, 8, 8, 8, 8, 2]);
 /// ```
-pub fn split_with_remainder(total: usize, max_batch_size: usize) -> Result<(usize, Vec<NonZeroUsize>, usize), String> {
-    if total == 0 {
-        return Err(String::from("Total must be a positive number"));
-    }
-    if max_batch_size == 0 {
-        return Err(String::from("Max batch size must be a positive number"));
-    }
-
-    let num_batches = total / max_batch_size;
-    let remainder = total % max_batch_size;
-
-    if num_batches == 0 {
-        Ok((1, vec![NonZeroUsize::new(total).unwrap()], 0))
-    } else {
-        Ok((
-            num_batches,
-            vec![NonZeroUsize::new(max_batch_size).unwrap(); num_batches],
-            remainder
-        ))
-    }
+pub fn split_with_remainder_batched(total: usize, max_batch_size: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let (mut batch_sizes, remainder) = split_with_optional_remainder(total, max_batch_size)?;
+    batch_sizes.extend(remainder);
+    Ok(batch_sizes)
 }
 
 #[cfg(test)]
@@ -433,7 +1550,7 @@ mod tests {
     fn test_even_split_basic() {
         assert_eq!(even_split(50, 8), Ok((10, vec![NonZeroUsize::new(5).unwrap(); 10])));
         assert_eq!(even_split(128, 8), Ok((16, vec![NonZeroUsize::new(8).unwrap(); 16])));
-        assert_eq!(even_split(46, 8), Ok((2, vec![NonZeroUsize::new(23).unwrap(); 2])));
+        assert_eq!(even_split(46, 8), Ok((23, vec![NonZeroUsize::new(2).unwrap(); 23])));
         assert_eq!(even_split(7, 8), Ok((1, vec![NonZeroUsize::new(7).unwrap()])));
     }
 
@@ -456,8 +1573,44 @@ mod tests {
 
     #[test]
     fn test_even_split_prime_numbers() {
-        assert_eq!(even_split(17, 8), Ok((1, vec![NonZeroUsize::new(17).unwrap()])));
-        assert_eq!(even_split(23, 8), Ok((1, vec![NonZeroUsize::new(23).unwrap()])));
+        assert_eq!(even_split(17, 8), Ok((17, vec![NonZeroUsize::new(1).unwrap(); 17])));
+        assert_eq!(even_split(23, 8), Ok((23, vec![NonZeroUsize::new(1).unwrap(); 23])));
+    }
+
+    #[test]
+    fn test_total_bounds_round_trip() {
+        for num_batches in 1..40 {
+            for max_batch_size in 1..40 {
+                let smallest = smallest_total_for(num_batches, max_batch_size).unwrap();
+                let largest = largest_total_for(num_batches, max_batch_size).unwrap();
+                assert!(smallest <= largest);
+                for total in [smallest, largest] {
+                    let (count, batch_sizes) = even_split(total, max_batch_size).unwrap();
+                    assert_eq!(count, num_batches);
+                    assert!(batch_sizes.iter().all(|&b| b == batch_sizes[0] && b.get() <= max_batch_size));
+                }
+                for total in 1..smallest {
+                    assert_ne!(even_split(total, max_batch_size).unwrap().0, num_batches);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_bounds_errors() {
+        assert!(smallest_total_for(0, 8).is_err());
+        assert!(smallest_total_for(5, 0).is_err());
+        assert!(largest_total_for(0, 8).is_err());
+        assert!(largest_total_for(5, 0).is_err());
+        assert!(largest_total_for(usize::MAX, 2).is_err());
+        assert_eq!(smallest_total_for(2, usize::MAX), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_smallest_total_for_large_max_batch_size() {
+        assert_eq!(smallest_total_for(2, usize::MAX / 2), Ok(usize::MAX / 2 + 1));
+        assert_eq!(smallest_total_for(4, 1 << 30), Ok((1 << 31) + 8));
+        assert_eq!(smallest_total_for(6, 1 << 30), Ok(3 * ((1 << 30) + 2)));
     }
 
     #[test]
@@ -473,6 +1626,32 @@ mod tests {
         assert!(split_by_count(10, 0).is_err());
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_split_across_cores() {
+        let cores = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let batch_sizes = split_across_cores(cores * 10 + 1).unwrap();
+        assert_eq!(batch_sizes.len(), cores);
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), cores * 10 + 1);
+        assert!(split_across_cores(0).is_err());
+    }
+
+    #[test]
+    fn test_split_by_count_alternating() {
+        let sizes = |total, n| split_by_count_alternating(total, n).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(sizes(12, 5), vec![3, 2, 2, 2, 3]);
+        assert_eq!(sizes(13, 5), vec![3, 3, 2, 2, 3]);
+        assert_eq!(sizes(14, 5), vec![3, 3, 2, 3, 3]);
+        assert_eq!(sizes(10, 5), vec![2; 5]);
+        let mut alternating = split_by_count_alternating(103, 10).unwrap();
+        alternating.sort();
+        let mut front = split_by_count(103, 10).unwrap();
+        front.sort();
+        assert_eq!(alternating, front);
+        assert!(split_by_count_alternating(0, 4).is_err());
+        assert!(split_by_count_alternating(10, 0).is_err());
+    }
+
     #[test]
     fn test_split_with_remainder() {
         assert_eq!(split_with_remainder(50, 8), Ok((6, vec![NonZeroUsize::new(8).unwrap(); 6], 2)));
@@ -486,6 +1665,15 @@ mod tests {
         assert!(split_with_remainder(10, 0).is_err());
     }
 
+    #[test]
+    fn test_split_with_optional_remainder() {
+        assert_eq!(split_with_optional_remainder(48, 8), Ok((vec![NonZeroUsize::new(8).unwrap(); 6], None)));
+        assert_eq!(split_with_optional_remainder(50, 8), Ok((vec![NonZeroUsize::new(8).unwrap(); 6], NonZeroUsize::new(2))));
+        assert_eq!(split_with_optional_remainder(5, 8), Ok((vec![NonZeroUsize::new(5).unwrap()], None)));
+        assert!(split_with_optional_remainder(0, 8).is_err());
+        assert!(split_with_optional_remainder(8, 0).is_err());
+    }
+
     #[test]
     fn test_split_weighted() {
         assert_eq!(split_weighted(100, vec![1, 2, 3]), Ok(vec![NonZeroUsize::new(17).unwrap(), NonZeroUsize::new(33).unwrap(), NonZeroUsize::new(50).unwrap()]));
@@ -502,7 +1690,34 @@ mod tests {
     #[test]
     fn test_split_range() {
         assert_eq!(split_range(100, 20, 40), Ok(vec![(3, 33, 1), (4, 25, 0), (5, 20, 0)]));
-        assert_eq!(split_range(10, 2, 5), Ok(vec![(2, 5, 0), (3, 3, 1), (4, 2, 2)]));
+        assert_eq!(split_range(10, 2, 5), Ok(vec![(2, 5, 0), (3, 3, 1), (4, 2, 2), (5, 2, 0)]));
+    }
+
+    #[test]
+    fn test_expand_range_config() {
+        for config in split_range(100, 7, 40).unwrap() {
+            let batch_sizes = expand_range_config(config).unwrap();
+            assert_eq!(batch_sizes.len(), config.0);
+            assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 100);
+            assert!(batch_sizes.iter().all(|b| (7..=40).contains(&b.get())));
+        }
+        assert_eq!(expand_range_config((0, 5, 0)), Err(BatchError::ZeroTotal));
+        assert_eq!(expand_range_config((3, 0, 2)), Err(BatchError::InsufficientTotal { total: 2, min_required: 3 }));
+        assert_eq!(expand_range_config((usize::MAX, 2, 0)), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_split_range_iter_and_limited() {
+        for (total, min, max) in [(100, 20, 40), (10, 2, 5), (97, 1, 97), (5, 6, 9)] {
+            let all = split_range(total, min, max).unwrap();
+            assert_eq!(split_range_iter(total, min, max).unwrap().collect::<Vec<_>>(), all);
+            for limit in 0..=all.len() + 1 {
+                assert_eq!(split_range_limited(total, min, max, limit).unwrap(), all[..limit.min(all.len())]);
+            }
+        }
+        assert_eq!(split_range_iter(usize::MAX, 1, usize::MAX).unwrap().next_back(), Some((usize::MAX, 1, 0)));
+        assert!(split_range_iter(0, 1, 2).is_err());
+        assert_eq!(split_range_limited(10, 3, 2, 5), Err(BatchError::MinExceedsMax { min: 3, max: 2 }));
     }
 
     #[test]
@@ -522,7 +1737,7 @@ mod tests {
     fn test_optimize_split_errors() {
         assert!(optimize_split(0, 3, 5).is_err());
         assert!(optimize_split(100, 0, 5).is_err());
-        assert!(optimize_split(100, 5, 3).is_err());
+        assert_eq!(optimize_split(100, 5, 3), Err(BatchError::MinBatchesExceedMax { min: 5, max: 3 }));
     }
 
     #[test]
@@ -531,11 +1746,371 @@ mod tests {
         assert_eq!(split_with_min_batch(50, 20, 10), Ok((3, vec![NonZeroUsize::new(17).unwrap(), NonZeroUsize::new(17).unwrap(), NonZeroUsize::new(16).unwrap()])));
     }
 
+    #[test]
+    fn test_split_with_min_batch_large_total() {
+        assert_eq!(split_with_min_batch(usize::MAX, usize::MAX, 3), Ok((1, vec![NonZeroUsize::new(usize::MAX).unwrap()])));
+        let half = usize::MAX / 2;
+        assert_eq!(
+            split_with_min_batch(usize::MAX, half + 1, 3),
+            Ok((2, vec![NonZeroUsize::new(half + 1).unwrap(), NonZeroUsize::new(half).unwrap()]))
+        );
+    }
+
     #[test]
     fn test_split_with_min_batch_errors() {
         assert!(split_with_min_batch(0, 30, 20).is_err());
         assert!(split_with_min_batch(100, 0, 20).is_err());
         assert!(split_with_min_batch(100, 30, 40).is_err());
         assert!(split_with_min_batch(100, 30, 31).is_err());
+        assert!(matches!(split_with_min_batch(7, 5, 4), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_with_min_batch_respects_bounds() {
+        for total in 1..150 {
+            for max in 1..25 {
+                for min in 0..=max {
+                    if let Ok((num_batches, batch_sizes)) = split_with_min_batch(total, max, min) {
+                        assert_eq!(num_batches, batch_sizes.len());
+                        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                        assert!(batch_sizes.iter().all(|b| b.get() <= max && b.get() >= min));
+                    }
+                }
+            }
+        }
+        assert_eq!(split_with_min_batch(100, 21, 20), Ok((5, vec![NonZeroUsize::new(20).unwrap(); 5])));
+        assert_eq!(split_with_min_batch(7, 5, 0).unwrap().1.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![4, 3]);
+    }
+
+    #[test]
+    fn test_split_with_fallback() {
+        for (total, primary_workers, fallback_workers) in [(100, 8, 3), (17, 17, 1), (1000, 12, 12)] {
+            let (primary, fallback) = split_with_fallback(total, primary_workers, fallback_workers).unwrap();
+            assert_eq!(primary.len(), primary_workers);
+            assert_eq!(fallback.len(), fallback_workers);
+            assert_eq!(primary.iter().map(|b| b.get()).sum::<usize>(), total);
+            assert_eq!(fallback.iter().map(|b| b.get()).sum::<usize>(), total);
+        }
+        assert!(split_with_fallback(0, 2, 1).is_err());
+        assert!(split_with_fallback(10, 0, 1).is_err());
+        assert!(split_with_fallback(10, 2, 0).is_err());
+        assert!(split_with_fallback(10, 11, 1).is_err());
+        assert!(split_with_fallback(10, 2, 11).is_err());
+    }
+
+    #[test]
+    fn test_mixed_radix_split_exact() {
+        assert_eq!(mixed_radix_split(72, &[1, 8, 32]), Ok(vec![(32, 2), (8, 1)]));
+        assert_eq!(mixed_radix_split(96, &[32]), Ok(vec![(32, 3)]));
+    }
+
+    #[test]
+    fn test_mixed_radix_split_filler() {
+        let groups = mixed_radix_split(100, &[1, 8, 32]).unwrap();
+        assert_eq!(groups, vec![(32, 3), (1, 4)]);
+        assert_eq!(groups.iter().map(|&(size, count)| size * count).sum::<usize>(), 100);
+        assert_eq!(mixed_radix_split(3, &[1, 8, 32]), Ok(vec![(1, 3)]));
+    }
+
+    #[test]
+    fn test_mixed_radix_split_errors() {
+        assert!(mixed_radix_split(100, &[8, 32]).is_err());
+        assert!(mixed_radix_split(0, &[1, 8]).is_err());
+        assert!(mixed_radix_split(10, &[]).is_err());
+        assert!(mixed_radix_split(10, &[0, 8]).is_err());
+        assert!(mixed_radix_split(10, &[8, 1]).is_err());
+        assert!(mixed_radix_split(10, &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_error_variants() {
+        assert_eq!(even_split(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(even_split(10, 0), Err(BatchError::ZeroMaxBatchSize));
+        assert_eq!(split_range(10, 0, 4), Err(BatchError::ZeroMinBatchSize));
+        assert_eq!(split_range(10, 5, 4), Err(BatchError::MinExceedsMax { min: 5, max: 4 }));
+        assert_eq!(split_weighted(10, vec![]), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted(10, vec![1, 2, 0, 3]), Err(BatchError::ZeroWeight { index: 2 }));
+        assert_eq!(split_by_count(10, 0), Err(BatchError::ZeroBatchCount));
+        assert_eq!(split_by_count(2, 5), Err(BatchError::InsufficientTotal { total: 2, min_required: 5 }));
+        assert_eq!(largest_total_for(usize::MAX, 2), Err(BatchError::Overflow));
+        assert_eq!(even_split(0, 8).unwrap_err().to_string(), "Total (0) must be a positive number");
+        assert_eq!(split_weighted(10, vec![1, 2, 0, 3]).unwrap_err().to_string(), "Weight at index 2 is zero; all weights must be positive numbers");
+    }
+
+    #[test]
+    fn test_optimize_split_report() {
+        for total in 1..120 {
+            for min in 1..10 {
+                for max in min..15 {
+                    let report = optimize_split_report(total, min, max);
+                    let split = optimize_split(total, min, max);
+                    assert_eq!(report.clone().map(|(n, sizes, _)| (n, sizes)), split);
+                    if let Ok((_, _, remainder)) = report {
+                        let best = (min..=max.min(total)).map(|n| total % n).min().unwrap();
+                        assert_eq!(remainder, best);
+                    }
+                }
+            }
+        }
+        assert_eq!(optimize_split_report(0, 1, 2), Err(BatchError::ZeroTotal));
+    }
+
+    #[test]
+    fn test_optimize_split_with_preferences() {
+        assert_eq!(optimize_split_with(100, 3, 5, CountPreference::default()), optimize_split(100, 3, 5));
+        assert_eq!(optimize_split_with(100, 2, 10, CountPreference::Most).unwrap().0, 10);
+        assert_eq!(optimize_split_with(100, 2, 10, CountPreference::BalancedSize).unwrap().0, 4);
+        assert_eq!(optimize_split_with(100, 3, 3, CountPreference::Most).unwrap().0, 3);
+        for total in 1..60 {
+            for min in 1..=total {
+                for max in min..70 {
+                    let remainders: Vec<usize> = (min..=max.min(total)).map(|n| total % n).collect();
+                    let best = *remainders.iter().min().unwrap();
+                    for preference in [CountPreference::Fewest, CountPreference::Most, CountPreference::BalancedSize] {
+                        let (num_batches, batch_sizes) = optimize_split_with(total, min, max, preference).unwrap();
+                        assert_eq!(total % num_batches, best);
+                        assert!((min..=max).contains(&num_batches));
+                        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                    }
+                }
+            }
+        }
+        assert_eq!(optimize_split_with(3, 5, 6, CountPreference::Fewest), Err(BatchError::InsufficientTotal { total: 3, min_required: 5 }));
+        assert_eq!(optimize_split_with(100, 6, 5, CountPreference::Most), Err(BatchError::MinBatchesExceedMax { min: 6, max: 5 }));
+    }
+
+    #[test]
+    fn test_split_with_min_and_max() {
+        for total in 1..120 {
+            for min in 1..12 {
+                for max in min..15 {
+                    let feasible = (1..=total).any(|n| n * min <= total && total <= n * max);
+                    match split_with_min_and_max(total, min, max) {
+                        Ok(sizes) => {
+                            assert!(feasible);
+                            assert_eq!(sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                            assert!(sizes.iter().all(|b| (min..=max).contains(&b.get())));
+                        }
+                        Err(error) => {
+                            assert!(!feasible);
+                            assert!(matches!(error, BatchError::ImpossibleConstraint(_)));
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(split_with_min_and_max(10, 0, 5), Err(BatchError::ZeroMinBatchSize));
+        assert_eq!(split_with_min_and_max(10, 6, 5), Err(BatchError::MinExceedsMax { min: 6, max: 5 }));
+        assert_eq!(split_with_min_and_max(usize::MAX, usize::MAX, usize::MAX).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_split_near_target() {
+        for total in 1..120 {
+            for target in 1..25 {
+                let batch_sizes = split_near_target(total, target).unwrap();
+                let deviation = |sizes: &[NonZeroUsize]| sizes.iter().map(|b| b.get().abs_diff(target)).sum::<usize>();
+                let best = (1..=total).map(|n| deviation(&split_by_count(total, n).unwrap())).min().unwrap();
+                let first_best = (1..=total).find(|&n| deviation(&split_by_count(total, n).unwrap()) == best).unwrap();
+                assert_eq!(batch_sizes.len(), first_best);
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+            }
+        }
+        assert_eq!(split_near_target(10, 4).unwrap().len(), 2);
+        assert_eq!(split_near_target(usize::MAX, usize::MAX / 2).unwrap().len(), 2);
+        assert_eq!(split_near_target(0, 4), Err(BatchError::ZeroTotal));
+        assert!(split_near_target(10, 0).is_err());
+    }
+
+    #[test]
+    fn test_even_split_ceil() {
+        for total in 1..150 {
+            for max in 1..30 {
+                let (num_batches, batch_sizes) = even_split_ceil(total, max).unwrap();
+                assert_eq!(num_batches, batch_sizes.len());
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                assert!(batch_sizes.iter().all(|b| b.get() <= max));
+                assert!(num_batches <= even_split(total, max).unwrap().0);
+                assert!((num_batches - 1) * max < total);
+            }
+        }
+        assert_eq!(even_split_ceil(usize::MAX, usize::MAX), Ok((1, vec![NonZeroUsize::MAX])));
+        assert_eq!(even_split_ceil(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(even_split_ceil(8, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_split_by_count_array_matches_split_by_count() {
+        for total in 0..100 {
+            assert_eq!(split_by_count_array::<1>(total).map(Vec::from), split_by_count(total, 1));
+            assert_eq!(split_by_count_array::<4>(total).map(Vec::from), split_by_count(total, 4));
+            assert_eq!(split_by_count_array::<7>(total).map(Vec::from), split_by_count(total, 7));
+        }
+        assert_eq!(split_by_count_array::<0>(10), Err(BatchError::ZeroBatchCount));
+        assert_eq!(split_by_count_array::<2>(usize::MAX).unwrap()[0].get(), usize::MAX / 2 + 1);
+    }
+
+    #[test]
+    fn test_enumerate_split() {
+        let batches = enumerate_split(100, 7).unwrap();
+        assert_eq!(batches.iter().map(|&(index, _)| index).collect::<Vec<_>>(), (0..7).collect::<Vec<_>>());
+        assert_eq!(batches.iter().map(|&(_, size)| size).collect::<Vec<_>>(), split_by_count(100, 7).unwrap());
+        assert_eq!(enumerate_split(2, 5), Err(BatchError::InsufficientTotal { total: 2, min_required: 5 }));
+    }
+
+    #[test]
+    fn test_split_by_count_clamped() {
+        for total in 1..40 {
+            for num_batches in 1..50 {
+                let batch_sizes = split_by_count_clamped(total, num_batches).unwrap();
+                if num_batches <= total {
+                    assert_eq!(Ok(batch_sizes), split_by_count(total, num_batches));
+                } else {
+                    assert_eq!(batch_sizes, vec![NonZeroUsize::MIN; total]);
+                }
+            }
+        }
+        assert_eq!(split_by_count_clamped(0, 3), Err(BatchError::ZeroTotal));
+        assert_eq!(split_by_count_clamped(3, 0), Err(BatchError::ZeroBatchCount));
+    }
+
+    #[test]
+    fn test_split_pow2() {
+        for total in 1..300 {
+            for max in 1..70 {
+                let batch_sizes = split_pow2(total, max).unwrap();
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                assert!(batch_sizes.iter().all(|b| b.get().is_power_of_two() && b.get() <= max));
+                assert!(batch_sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+            }
+        }
+        assert_eq!(split_pow2(usize::MAX, usize::MAX).unwrap().len(), usize::BITS as usize);
+        assert_eq!(split_pow2(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(split_pow2(8, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_split_aligned() {
+        for total in 1..300 {
+            for max in 1..70 {
+                for alignment in 1..=max {
+                    let batch_sizes = split_aligned(total, max, alignment).unwrap();
+                    assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                    assert!(batch_sizes.iter().all(|b| b.get() <= max));
+                    assert!(batch_sizes[..batch_sizes.len() - 1].iter().all(|b| b.get() % alignment == 0));
+                }
+            }
+        }
+        assert_eq!(split_aligned(20, 64, 32).unwrap(), vec![NonZeroUsize::new(20).unwrap()]);
+        assert_eq!(split_aligned(0, 64, 32), Err(BatchError::ZeroTotal));
+        assert_eq!(split_aligned(10, 0, 32), Err(BatchError::ZeroMaxBatchSize));
+        assert!(split_aligned(10, 64, 0).is_err());
+        assert!(split_aligned(10, 16, 32).is_err());
+    }
+
+    #[test]
+    fn test_split_with_remainder_batched() {
+        for total in 1..200 {
+            for max in 1..30 {
+                let batch_sizes = split_with_remainder_batched(total, max).unwrap();
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                assert!(batch_sizes.iter().all(|b| b.get() <= max));
+                assert!(batch_sizes[..batch_sizes.len() - 1].iter().all(|b| b.get() == max.min(total)));
+            }
+        }
+        assert_eq!(split_with_remainder_batched(10, 20), Ok(vec![NonZeroUsize::new(10).unwrap()]));
+        assert_eq!(split_with_remainder_batched(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(split_with_remainder_batched(8, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_even_split_saturating() {
+        for total in 1..100 {
+            for max in 1..20 {
+                assert_eq!(even_split_saturating(total, max), even_split(total, max).unwrap());
+            }
+            assert_eq!(even_split_saturating(total, 0), even_split(total, 1).unwrap());
+        }
+        assert_eq!(even_split_saturating(0, 8), (0, Vec::new()));
+        assert_eq!(even_split_saturating(0, 0), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_count_batches_matches_even_split() {
+        for total in 0..200 {
+            for max in 0..40 {
+                assert_eq!(count_batches(total, max), even_split(total, max).map(|(n, _)| n));
+            }
+        }
+        assert_eq!(count_batches(usize::MAX, usize::MAX), Ok(1));
+    }
+
+    #[test]
+    fn test_split_weighted_iter() {
+        let sizes = |batch_sizes: Vec<NonZeroUsize>| batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(split_weighted_iter(100, [1, 2, 3]).map(sizes), Ok(vec![17, 33, 50]));
+        assert_eq!(split_weighted_iter(100, (1..=3).rev()).map(sizes), Ok(vec![50, 33, 17]));
+        assert_eq!(split_weighted_iter(7, core::iter::once(5)).map(sizes), Ok(vec![7]));
+        let weights: std::collections::VecDeque<usize> = [2, 1].into_iter().collect();
+        assert_eq!(split_weighted_iter(9, weights).map(sizes), Ok(vec![6, 3]));
+
+        assert_eq!(split_weighted_iter(10, core::iter::empty()), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted_iter(10, [7, 0, 1]), Err(BatchError::ZeroWeight { index: 1 }));
+        assert_eq!(split_weighted_iter(0, [1, 2, 3]), Err(BatchError::ZeroTotal));
+    }
+
+    #[test]
+    fn test_split_weighted_large_total() {
+        let total = usize::MAX / 2;
+        let batch_sizes = split_weighted(total, vec![3, 4]).unwrap();
+        let expected = ((total as u128 * 3 + 3) / 7) as usize;
+        assert_eq!(batch_sizes, vec![NonZeroUsize::new(expected).unwrap(), NonZeroUsize::new(total - expected).unwrap()]);
+    }
+
+    #[test]
+    fn test_split_weighted_too_small_total_is_an_error() {
+        assert_eq!(split_weighted(2, vec![1, 1, 1]), Err(BatchError::InsufficientTotal { total: 2, min_required: 3 }));
+    }
+
+    #[test]
+    fn test_split_by_count_sorted() {
+        for total in 1..60 {
+            for n in 1..=total {
+                let front = split_by_count(total, n).unwrap();
+                let descending = split_by_count_sorted(total, n, true).unwrap();
+                let ascending = split_by_count_sorted(total, n, false).unwrap();
+                assert_eq!(descending, front);
+                assert!(ascending.windows(2).all(|pair| pair[0] <= pair[1]));
+                let mut sorted = front.clone();
+                sorted.sort();
+                assert_eq!(ascending, sorted);
+            }
+        }
+        assert_eq!(split_by_count_sorted(2, 5, false), Err(BatchError::InsufficientTotal { total: 2, min_required: 5 }));
+    }
+
+    #[test]
+    fn test_split_by_count_with_strategies() {
+        let sizes = |total, n, strategy| split_by_count_with(total, n, strategy).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(sizes(10, 3, RemainderStrategy::Front), vec![4, 3, 3]);
+        assert_eq!(sizes(10, 3, RemainderStrategy::Back), vec![3, 3, 4]);
+        assert_eq!(sizes(10, 3, RemainderStrategy::Spread), vec![3, 4, 3]);
+        assert_eq!(sizes(12, 5, RemainderStrategy::Spread), vec![2, 3, 2, 3, 2]);
+        assert_eq!(sizes(23, 10, RemainderStrategy::Spread), vec![2, 3, 2, 2, 2, 3, 2, 2, 3, 2]);
+        for (total, n) in [(10, 3), (103, 10), (99, 100), (7, 7), (1000, 999)] {
+            let front = split_by_count(total, n);
+            for strategy in [RemainderStrategy::Front, RemainderStrategy::Back, RemainderStrategy::Spread] {
+                let result = split_by_count_with(total, n, strategy);
+                assert_eq!(result.is_ok(), front.is_ok());
+                if let (Ok(mut batches), Ok(mut expected)) = (result, front.clone()) {
+                    assert_eq!(batches.len(), n);
+                    batches.sort();
+                    expected.sort();
+                    assert_eq!(batches, expected);
+                }
+            }
+        }
+        assert_eq!(split_by_count_with(0, 3, RemainderStrategy::Spread), Err(BatchError::ZeroTotal));
+        assert_eq!(split_by_count_with(3, 0, RemainderStrategy::Back), Err(BatchError::ZeroBatchCount));
     }
 }
@@ -0,0 +1,78 @@
+//! Parallel iterators over batches, available with the `rayon` feature.
+
+use core::ops::Range;
+
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::BatchError;
+
+/// Splits `0..total` into `num_batches` contiguous half-open ranges, yielded in parallel.
+///
+/// The ranges are the same as those of [`split_offsets`](crate::split_offsets), but each one is
+/// computed from its index, so no vector of ranges is allocated. The returned iterator is
+/// indexed, so it can be zipped or collected in order.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of ranges to produce.
+///
+/// # Returns
+///
+/// A `Result` containing a parallel iterator over the ranges.
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_by_count`](crate::split_by_count).
+///
+/// # Examples
+///
+/// ```
+/// use rayon::iter::ParallelIterator;
+/// use rsbatch_maestro::par_split_offsets;
+///
+/// let data: Vec<u64> = (1..=100).collect();
+/// let sum: u64 = par_split_offsets(data.len(), 8).unwrap().map(|range| data[range].iter().sum::<u64>()).sum();
+/// assert_eq!(sum, 5050);
+/// ```
+pub fn par_split_offsets(total: usize, num_batches: usize) -> Result<impl IndexedParallelIterator<Item = Range<usize>>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if total < num_batches {
+        return Err(BatchError::InsufficientTotal { total, min_required: num_batches });
+    }
+
+    let base_size = total / num_batches;
+    let remainder = total % num_batches;
+    let start = move |i: usize| i * base_size + i.min(remainder);
+    Ok((0..num_batches).into_par_iter().map(move |i| start(i)..start(i + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split_offsets;
+
+    #[test]
+    fn test_matches_split_offsets() {
+        for total in 1..80 {
+            for num_batches in 1..=total {
+                let ranges: Vec<Range<usize>> = par_split_offsets(total, num_batches).unwrap().collect();
+                assert_eq!(ranges, split_offsets(total, num_batches).unwrap());
+            }
+        }
+        let last = par_split_offsets(usize::MAX, 3).unwrap().collect::<Vec<_>>().pop();
+        assert_eq!(last.map(|range| range.end), Some(usize::MAX));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(par_split_offsets(0, 4), Err(BatchError::ZeroTotal)));
+        assert!(matches!(par_split_offsets(4, 0), Err(BatchError::ZeroBatchCount)));
+        assert!(matches!(par_split_offsets(3, 4), Err(BatchError::InsufficientTotal { total: 3, min_required: 4 })));
+    }
+}
@@ -0,0 +1,394 @@
+//! A self-describing record of a computed split.
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::{even_split, expand_range_config, split_by_count, split_range, split_weighted, split_with_min_batch, split_with_remainder, BatchError};
+
+/// A computed split together with the total it covers.
+///
+/// Unlike the tuples returned by the splitting functions, a plan carries its own total, so it
+/// can be stored and checked later with [`validate`](BatchPlan::validate). With the `serde`
+/// feature enabled, plans implement `Serialize` and `Deserialize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchPlan {
+    /// The total the plan was computed for.
+    pub total: usize,
+    /// The batch sizes.
+    pub sizes: Vec<NonZeroUsize>,
+    /// The part of the total not covered by any batch.
+    pub remainder: usize,
+}
+
+impl BatchPlan {
+    /// Checks that the batch sizes plus the remainder add up to the total.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BatchError::Overflow` if the sum overflows `usize`, and
+    /// `BatchError::SumMismatch` if it differs from the total.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbatch_maestro::split_with_remainder_plan;
+    ///
+    /// let mut plan = split_with_remainder_plan(50, 8).unwrap();
+    /// assert!(plan.validate().is_ok());
+    /// plan.remainder += 1;
+    /// assert!(plan.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), BatchError> {
+        let covered = sum_sizes(self.remainder, &self.sizes)?;
+        if covered != self.total {
+            return Err(BatchError::SumMismatch { expected: self.total, actual: covered });
+        }
+        Ok(())
+    }
+}
+
+/// Builds a plan from the `(number of batches, sizes)` tuple returned by functions such as
+/// [`even_split`].
+///
+/// The total is the sum of the sizes and the remainder is zero. A sum that overflows `usize`
+/// saturates, which [`validate`](BatchPlan::validate) then reports.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{even_split, BatchPlan};
+///
+/// let plan: BatchPlan = even_split(100, 8).unwrap().into();
+/// assert_eq!((plan.total, plan.sizes.len(), plan.remainder), (100, 20, 0));
+/// ```
+impl From<(usize, Vec<NonZeroUsize>)> for BatchPlan {
+    fn from((_, sizes): (usize, Vec<NonZeroUsize>)) -> Self {
+        BatchPlan::from((0, sizes, 0))
+    }
+}
+
+/// Builds a plan from the `(number of batches, sizes, remainder)` tuple returned by
+/// [`split_with_remainder`].
+///
+/// The total is the sum of the sizes plus the remainder. A sum that overflows `usize`
+/// saturates, which [`validate`](BatchPlan::validate) then reports.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_with_remainder, BatchPlan};
+///
+/// let plan: BatchPlan = split_with_remainder(50, 8).unwrap().into();
+/// assert_eq!((plan.total, plan.sizes.len(), plan.remainder), (50, 6, 2));
+/// ```
+impl From<(usize, Vec<NonZeroUsize>, usize)> for BatchPlan {
+    fn from((_, sizes, remainder): (usize, Vec<NonZeroUsize>, usize)) -> Self {
+        let total = sizes.iter().fold(remainder, |sum, size| sum.saturating_add(size.get()));
+        BatchPlan { total, sizes, remainder }
+    }
+}
+
+/// Adds the batch sizes to `start`, failing if the sum overflows `usize`.
+fn sum_sizes(start: usize, batches: &[NonZeroUsize]) -> Result<usize, BatchError> {
+    batches
+        .iter()
+        .try_fold(start, |sum, size| sum.checked_add(size.get()))
+        .ok_or(BatchError::Overflow)
+}
+
+/// Checks that batch sizes add up to exactly `total`.
+///
+/// This is a defensive check for splits that come from outside the crate, such as a deserialized
+/// plan, before work is dispatched according to them.
+///
+/// # Arguments
+///
+/// * `total` - The total the batches should cover.
+/// * `batches` - The batch sizes to check.
+///
+/// # Returns
+///
+/// `Ok(())` if the sizes add up to the total.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The sum of the sizes overflows `usize`.
+/// * The sum of the sizes differs from the total, as `BatchError::SumMismatch`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_by_count, verify_split, BatchError};
+/// use std::num::NonZeroUsize;
+///
+/// assert!(verify_split(10, &split_by_count(10, 3).unwrap()).is_ok());
+/// let batches = [NonZeroUsize::new(4).unwrap(); 2];
+/// assert_eq!(verify_split(10, &batches), Err(BatchError::SumMismatch { expected: 10, actual: 8 }));
+/// ```
+pub fn verify_split(total: usize, batches: &[NonZeroUsize]) -> Result<(), BatchError> {
+    let actual = sum_sizes(0, batches)?;
+    if actual != total {
+        return Err(BatchError::SumMismatch { expected: total, actual });
+    }
+    Ok(())
+}
+
+/// Checks that batch sizes add up to exactly `total` and each lies within `[min, max]`.
+///
+/// # Arguments
+///
+/// * `total` - The total the batches should cover.
+/// * `batches` - The batch sizes to check.
+/// * `min_batch_size` - The minimum allowed size, inclusive.
+/// * `max_batch_size` - The maximum allowed size, inclusive.
+///
+/// # Returns
+///
+/// `Ok(())` if the sizes add up to the total and all lie within the bounds.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The min_batch_size is greater than max_batch_size.
+/// * Any size lies outside the bounds.
+/// * The sizes do not add up to the total, as in [`verify_split`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_by_count, verify_split_bounded};
+///
+/// let batches = split_by_count(10, 3).unwrap();
+/// assert!(verify_split_bounded(10, &batches, 3, 4).is_ok());
+/// assert!(verify_split_bounded(10, &batches, 4, 4).is_err());
+/// ```
+pub fn verify_split_bounded(total: usize, batches: &[NonZeroUsize], min_batch_size: usize, max_batch_size: usize) -> Result<(), BatchError> {
+    if min_batch_size > max_batch_size {
+        return Err(BatchError::MinExceedsMax { min: min_batch_size, max: max_batch_size });
+    }
+    if let Some(index) = batches.iter().position(|size| !(min_batch_size..=max_batch_size).contains(&size.get())) {
+        return Err(BatchError::InvalidInput(format!(
+            "Batch {} has size {}, outside {}..={}",
+            index, batches[index], min_batch_size, max_batch_size
+        )));
+    }
+    verify_split(total, batches)
+}
+
+/// Splits a total number into even batches, returning the result as a [`BatchPlan`].
+///
+/// The sizes are those of [`even_split`], which always covers the whole total, so the remainder
+/// is zero.
+///
+/// # Errors
+///
+/// Returns the same errors as [`even_split`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::even_split_plan;
+///
+/// let plan = even_split_plan(50, 8).unwrap();
+/// assert_eq!((plan.total, plan.sizes.len(), plan.remainder), (50, 10, 0));
+/// ```
+pub fn even_split_plan(total: usize, max_batch_size: usize) -> Result<BatchPlan, BatchError> {
+    let (_, sizes) = even_split(total, max_batch_size)?;
+    Ok(BatchPlan { total, sizes, remainder: 0 })
+}
+
+/// Splits a total number into full batches, returning the result as a [`BatchPlan`].
+///
+/// The sizes and remainder are those of [`split_with_remainder`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_with_remainder`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_remainder_plan;
+///
+/// let plan = split_with_remainder_plan(50, 8).unwrap();
+/// assert_eq!((plan.total, plan.sizes.len(), plan.remainder), (50, 6, 2));
+/// ```
+pub fn split_with_remainder_plan(total: usize, max_batch_size: usize) -> Result<BatchPlan, BatchError> {
+    let (_, sizes, remainder) = split_with_remainder(total, max_batch_size)?;
+    Ok(BatchPlan { total, sizes, remainder })
+}
+
+/// Generates every configuration of [`split_range`] as a ready-to-use [`BatchPlan`].
+///
+/// Each configuration is expanded with [`expand_range_config`], so the remainder is spread over
+/// the batches and every plan covers the whole total.
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_range`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_range_plans;
+///
+/// let plans = split_range_plans(100, 20, 40).unwrap();
+/// assert_eq!(plans.len(), 3);
+/// assert_eq!(plans[0].sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![34, 33, 33]);
+/// ```
+pub fn split_range_plans(total: usize, min_batch_size: usize, max_batch_size: usize) -> Result<Vec<BatchPlan>, BatchError> {
+    split_range(total, min_batch_size, max_batch_size)?
+        .into_iter()
+        .map(|config| Ok(BatchPlan { total, sizes: expand_range_config(config)?, remainder: 0 }))
+        .collect()
+}
+
+/// A splitting strategy chosen at runtime, for use with [`split`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SplitStrategy {
+    /// Uniform batches of at most `max` items, as in [`even_split`].
+    Even {
+        /// The maximum allowed size for each batch.
+        max: usize,
+    },
+    /// A fixed number of balanced batches, as in [`split_by_count`].
+    ByCount(usize),
+    /// Batches proportional to the weights, as in [`split_weighted`].
+    Weighted(Vec<usize>),
+    /// Batches within `[min, max]`, as in [`split_with_min_batch`].
+    MinBatch {
+        /// The minimum required size for each batch.
+        min: usize,
+        /// The maximum allowed size for each batch.
+        max: usize,
+    },
+}
+
+/// Splits a total number with a strategy selected at runtime, returning a [`BatchPlan`].
+///
+/// Each [`SplitStrategy`] variant dispatches to the function it names, so the plan's sizes are
+/// exactly what that function returns and the remainder is zero. This lets a caller map a
+/// configuration value or command-line flag to a split without matching on it itself.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `strategy` - The strategy and its parameters.
+///
+/// # Returns
+///
+/// A `Result` containing the plan covering the whole total.
+///
+/// # Errors
+///
+/// Returns the same errors as the function the strategy dispatches to.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split, SplitStrategy};
+///
+/// let plan = split(10, SplitStrategy::ByCount(3)).unwrap();
+/// assert_eq!(plan.sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![4, 3, 3]);
+/// let plan = split(50, SplitStrategy::Even { max: 8 }).unwrap();
+/// assert_eq!((plan.total, plan.sizes.len(), plan.remainder), (50, 10, 0));
+/// ```
+pub fn split(total: usize, strategy: SplitStrategy) -> Result<BatchPlan, BatchError> {
+    let sizes = match strategy {
+        SplitStrategy::Even { max } => even_split(total, max)?.1,
+        SplitStrategy::ByCount(num_batches) => split_by_count(total, num_batches)?,
+        SplitStrategy::Weighted(weights) => split_weighted(total, weights)?,
+        SplitStrategy::MinBatch { min, max } => split_with_min_batch(total, max, min)?.1,
+    };
+    Ok(BatchPlan { total, sizes, remainder: 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plans_validate() {
+        for (total, max) in [(50, 8), (7, 10), (128, 128), (1, 1)] {
+            assert!(even_split_plan(total, max).unwrap().validate().is_ok());
+            assert!(split_with_remainder_plan(total, max).unwrap().validate().is_ok());
+        }
+        assert_eq!(split_with_remainder_plan(0, 8), Err(BatchError::ZeroTotal));
+    }
+
+    #[test]
+    fn test_split_range_plans() {
+        let plans = split_range_plans(10, 2, 5).unwrap();
+        assert_eq!(plans.iter().map(|plan| plan.sizes.len()).collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        assert!(plans.iter().all(|plan| plan.validate().is_ok()));
+        assert_eq!(split_range_plans(10, 6, 5), Err(BatchError::MinExceedsMax { min: 6, max: 5 }));
+    }
+
+    #[test]
+    fn test_from_tuples() {
+        let plan = BatchPlan::from(split_with_remainder(50, 8).unwrap());
+        assert_eq!(plan, split_with_remainder_plan(50, 8).unwrap());
+        let plan = BatchPlan::from(even_split(50, 8).unwrap());
+        assert_eq!(plan, even_split_plan(50, 8).unwrap());
+        let plan = BatchPlan::from((2, vec![NonZeroUsize::MAX; 2]));
+        assert_eq!(plan.total, usize::MAX);
+        assert_eq!(plan.validate(), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_validate_mismatch() {
+        let plan = BatchPlan { total: 10, sizes: vec![NonZeroUsize::new(4).unwrap(); 2], remainder: 1 };
+        assert_eq!(plan.validate(), Err(BatchError::SumMismatch { expected: 10, actual: 9 }));
+        let plan = BatchPlan { total: 10, sizes: vec![NonZeroUsize::MAX], remainder: 1 };
+        assert_eq!(plan.validate(), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_verify_split() {
+        let batches = [NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(4).unwrap()];
+        assert_eq!(verify_split(7, &batches), Ok(()));
+        assert_eq!(verify_split(8, &batches), Err(BatchError::SumMismatch { expected: 8, actual: 7 }));
+        assert_eq!(verify_split(0, &[]), Ok(()));
+        assert_eq!(verify_split(1, &[NonZeroUsize::MAX, NonZeroUsize::MIN]), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_verify_split_bounded() {
+        let batches = [NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(4).unwrap()];
+        assert_eq!(verify_split_bounded(7, &batches, 3, 4), Ok(()));
+        assert!(matches!(verify_split_bounded(7, &batches, 1, 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(verify_split_bounded(7, &batches, 4, 9), Err(BatchError::InvalidInput(_))));
+        assert_eq!(verify_split_bounded(8, &batches, 1, 9), Err(BatchError::SumMismatch { expected: 8, actual: 7 }));
+        assert_eq!(verify_split_bounded(7, &batches, 5, 4), Err(BatchError::MinExceedsMax { min: 5, max: 4 }));
+    }
+
+    #[test]
+    fn test_split_matches_underlying_functions() {
+        assert_eq!(split(50, SplitStrategy::Even { max: 8 }).unwrap().sizes, even_split(50, 8).unwrap().1);
+        assert_eq!(split(10, SplitStrategy::ByCount(3)).unwrap().sizes, split_by_count(10, 3).unwrap());
+        assert_eq!(split(10, SplitStrategy::Weighted(vec![1, 2, 2])).unwrap().sizes, split_weighted(10, vec![1, 2, 2]).unwrap());
+        assert_eq!(split(100, SplitStrategy::MinBatch { min: 20, max: 30 }).unwrap().sizes, split_with_min_batch(100, 30, 20).unwrap().1);
+        assert!(split(100, SplitStrategy::ByCount(7)).unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn test_split_errors() {
+        assert_eq!(split(0, SplitStrategy::Even { max: 8 }), Err(BatchError::ZeroTotal));
+        assert_eq!(split(10, SplitStrategy::ByCount(0)), Err(BatchError::ZeroBatchCount));
+        assert_eq!(split(10, SplitStrategy::Weighted(Vec::new())), Err(BatchError::EmptyWeights));
+        assert_eq!(split(10, SplitStrategy::MinBatch { min: 6, max: 5 }), Err(BatchError::MinExceedsMax { min: 6, max: 5 }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_plan_json_round_trip() {
+        let plan = split_with_remainder_plan(50, 8).unwrap();
+        let json = serde_json::to_string(&plan).unwrap();
+        assert_eq!(json, r#"{"total":50,"sizes":[8,8,8,8,8,8],"remainder":2}"#);
+        assert_eq!(serde_json::from_str::<BatchPlan>(&json).unwrap(), plan);
+        assert!(serde_json::from_str::<BatchPlan>(r#"{"total":1,"sizes":[0],"remainder":0}"#).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Randomized strategies that produce uneven but reproducible splits, for load testing.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::BatchError;
+
+/// A xorshift64* generator, small enough to keep the crate free of a `rand` dependency.
+struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// Creates a generator from `seed`; the all-zero state, which xorshift never leaves, is
+    /// replaced by a fixed odd constant.
+    fn new(seed: u64) -> Self {
+        XorShift64 { state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed } }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a uniform value in `[0, 1)` built from the top 53 bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Splits a total into batches whose sizes vary randomly around a mean.
+///
+/// Each batch size is drawn uniformly from `mean_batch_size * (1 ± jitter)`, rounded to the
+/// nearest integer and at least one, from a xorshift generator seeded with `seed`, so the same
+/// arguments always give the same split. Batches are drawn until what is left fits in a single
+/// batch of the largest possible size; the last batch then absorbs that rest, so the sizes sum
+/// exactly to `total` and the last one may be smaller than the jitter range.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `mean_batch_size` - The size the batches vary around.
+/// * `jitter` - The largest relative deviation from the mean, in `[0, 1)`.
+/// * `seed` - The seed of the random sequence.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The mean_batch_size is zero.
+/// * The jitter is negative, not finite, or at least one.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_jittered;
+///
+/// let batch_sizes = split_jittered(1000, 100, 0.2, 42).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 1000);
+/// assert!(batch_sizes[..batch_sizes.len() - 1].iter().all(|b| (80..=120).contains(&b.get())));
+/// assert_eq!(batch_sizes, split_jittered(1000, 100, 0.2, 42).unwrap());
+/// ```
+pub fn split_jittered(total: usize, mean_batch_size: usize, jitter: f64, seed: u64) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if mean_batch_size == 0 {
+        return Err(BatchError::InvalidInput(String::from("Mean batch size must be a positive number")));
+    }
+    if !(0.0..1.0).contains(&jitter) {
+        return Err(BatchError::InvalidInput(String::from("Jitter must be at least 0 and less than 1")));
+    }
+
+    let mean = mean_batch_size as f64;
+    let largest = ((mean * (1.0 + jitter) + 0.5) as usize).max(1);
+    let mut rng = XorShift64::new(seed);
+    let mut batch_sizes = Vec::new();
+    let mut remaining = total;
+    while remaining > largest {
+        let factor = 1.0 + jitter * (2.0 * rng.next_f64() - 1.0);
+        let size = ((mean * factor + 0.5) as usize).clamp(1, largest);
+        batch_sizes.push(NonZeroUsize::new(size).unwrap());
+        remaining -= size;
+    }
+    batch_sizes.push(NonZeroUsize::new(remaining).unwrap());
+
+    Ok(batch_sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_jittered_bounds() {
+        for seed in 0..20 {
+            for (total, mean, jitter) in [(1000, 100, 0.2), (10_000, 7, 0.9), (5, 10, 0.5), (333, 1, 0.0), (500, 40, 0.0)] {
+                let batch_sizes = split_jittered(total, mean, jitter, seed).unwrap();
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                let low = (mean as f64 * (1.0 - jitter)) as usize;
+                let high = (mean as f64 * (1.0 + jitter)).ceil() as usize;
+                assert!(batch_sizes[..batch_sizes.len() - 1].iter().all(|b| (low.max(1)..=high).contains(&b.get())));
+                assert!(batch_sizes.last().unwrap().get() <= high);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_jittered_is_deterministic() {
+        let first = split_jittered(10_000, 50, 0.5, 7).unwrap();
+        assert_eq!(first, split_jittered(10_000, 50, 0.5, 7).unwrap());
+        assert_ne!(first, split_jittered(10_000, 50, 0.5, 8).unwrap());
+        assert!(first.iter().any(|b| b.get() != 50));
+    }
+
+    #[test]
+    fn test_split_jittered_errors() {
+        assert_eq!(split_jittered(0, 10, 0.1, 1), Err(BatchError::ZeroTotal));
+        assert!(split_jittered(10, 0, 0.1, 1).is_err());
+        assert!(split_jittered(10, 5, 1.0, 1).is_err());
+        assert!(split_jittered(10, 5, -0.1, 1).is_err());
+        assert!(split_jittered(10, 5, f64::NAN, 1).is_err());
+    }
+}
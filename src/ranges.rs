@@ -0,0 +1,696 @@
+//! Strategies that describe batches as index ranges rather than sizes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use core::ops::Range;
+
+use crate::{even_split, split_by_count, BatchError};
+
+/// Even-splits the half-open segment `start..end` and appends its batch ranges to `ranges`.
+fn push_even_ranges(ranges: &mut Vec<Range<usize>>, start: usize, end: usize, max_batch_size: usize) -> Result<(), BatchError> {
+    let (_, batch_sizes) = even_split(end - start, max_batch_size)?;
+    let mut offset = start;
+    for size in batch_sizes {
+        ranges.push(offset..offset + size.get());
+        offset += size.get();
+    }
+    Ok(())
+}
+
+/// Splits `0..total` into `num_batches` contiguous half-open ranges.
+///
+/// The range lengths follow [`split_by_count`], so the first `total % num_batches` ranges are one
+/// longer. The ranges do not overlap, each starts where the previous one ends, and the last one
+/// ends at `total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of ranges to produce.
+///
+/// # Returns
+///
+/// A `Result` containing the ranges in order.
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_by_count`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_offsets;
+///
+/// assert_eq!(split_offsets(10, 3).unwrap(), vec![0..4, 4..7, 7..10]);
+/// ```
+pub fn split_offsets(total: usize, num_batches: usize) -> Result<Vec<Range<usize>>, BatchError> {
+    let batch_sizes = split_by_count(total, num_batches)?;
+
+    let mut offset = 0;
+    let mut ranges = Vec::with_capacity(batch_sizes.len());
+    for size in batch_sizes {
+        ranges.push(offset..offset + size.get());
+        offset += size.get();
+    }
+
+    Ok(ranges)
+}
+
+/// Splits `0..total` into contiguous half-open ranges of the batches produced by [`even_split`].
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing the ranges in order.
+///
+/// # Errors
+///
+/// Returns the same errors as [`even_split`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::even_split_offsets;
+///
+/// assert_eq!(even_split_offsets(20, 8).unwrap(), vec![0..5, 5..10, 10..15, 15..20]);
+/// ```
+pub fn even_split_offsets(total: usize, max_batch_size: usize) -> Result<Vec<Range<usize>>, BatchError> {
+    let mut ranges = Vec::new();
+    push_even_ranges(&mut ranges, 0, total, max_batch_size)?;
+    Ok(ranges)
+}
+
+/// Returns the ranges that `slice::chunks(chunk_size)` yields for a slice of length `total`.
+///
+/// There are `total.div_ceil(chunk_size)` ranges, all `chunk_size` wide except possibly the
+/// last, which holds what is left. Unlike [`even_split_offsets`], which searches for a batch
+/// size that divides the total, this keeps every batch at exactly the maximum and leaves the
+/// short batch at the end. As with `chunks`, a total of zero yields no ranges.
+///
+/// # Arguments
+///
+/// * `total` - The length of the sequence to chunk.
+/// * `chunk_size` - The width of every chunk but the last.
+///
+/// # Returns
+///
+/// A `Result` containing the chunk ranges in order.
+///
+/// # Errors
+///
+/// Returns an error if the chunk_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::chunk_boundaries;
+///
+/// assert_eq!(chunk_boundaries(10, 4).unwrap(), vec![0..4, 4..8, 8..10]);
+/// ```
+pub fn chunk_boundaries(total: usize, chunk_size: usize) -> Result<Vec<Range<usize>>, BatchError> {
+    if chunk_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    Ok((0..total).step_by(chunk_size).map(|start| start..start + chunk_size.min(total - start)).collect())
+}
+
+/// Splits `0..total` at mandatory boundaries, even-splitting the segments between them.
+///
+/// Every value in `fixed` is a required cut position (for example a record boundary known in
+/// advance), so no returned range straddles it. Each segment between consecutive boundaries is
+/// then divided with [`even_split`], so no range is longer than `max_batch_size`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `fixed` - The mandatory boundary positions, strictly increasing and within `0..total`.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing contiguous, non-overlapping half-open ranges covering `0..total`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * The fixed boundaries are not strictly increasing or fall outside `0..total`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_fixed_boundaries;
+///
+/// let ranges = split_with_fixed_boundaries(10, &[2], 4).unwrap();
+/// assert_eq!(ranges, vec![0..2, 2..6, 6..10]);
+/// ```
+pub fn split_with_fixed_boundaries(total: usize, fixed: &[usize], max_batch_size: usize) -> Result<Vec<Range<usize>>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+    if fixed.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(BatchError::InvalidInput(String::from("Fixed boundaries must be strictly increasing")));
+    }
+    if fixed.last().is_some_and(|&last| last >= total) {
+        return Err(BatchError::InvalidInput(String::from("Fixed boundaries must lie within 0..total")));
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for &boundary in fixed.iter().chain(core::iter::once(&total)) {
+        if boundary > start {
+            push_even_ranges(&mut ranges, start, boundary, max_batch_size)?;
+        }
+        start = boundary;
+    }
+
+    Ok(ranges)
+}
+
+/// Converts a cumulative (prefix-sum) specification into batch sizes.
+///
+/// `target_prefix[i]` is the number of items covered by batches `0..=i`, so the batch sizes are
+/// the consecutive differences of the prefix, starting from zero. This lets a partition that is
+/// defined by cumulative counts elsewhere be validated and turned into sizes in one step.
+///
+/// # Arguments
+///
+/// * `total` - The total number being split.
+/// * `target_prefix` - The strictly increasing cumulative batch ends, the last equal to `total`.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The prefix is empty.
+/// * The prefix does not start above zero or is not strictly increasing.
+/// * The prefix does not end exactly at `total`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_to_match_prefix;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_to_match_prefix(10, &[3, 7, 10]).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// ```
+pub fn split_to_match_prefix(total: usize, target_prefix: &[usize]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if target_prefix.last() != Some(&total) {
+        return Err(BatchError::InvalidInput(String::from("Prefix sums must end exactly at the total")));
+    }
+
+    let mut previous = 0;
+    let mut batch_sizes = Vec::with_capacity(target_prefix.len());
+    for &end in target_prefix {
+        let size = end
+            .checked_sub(previous)
+            .and_then(NonZeroUsize::new)
+            .ok_or_else(|| BatchError::InvalidInput(String::from("Prefix sums must start above zero and be strictly increasing")))?;
+        batch_sizes.push(size);
+        previous = end;
+    }
+
+    Ok(batch_sizes)
+}
+
+/// The worker id given to gap batches by [`split_with_affinity`].
+pub const UNASSIGNED_WORKER: usize = usize::MAX;
+
+/// Splits `0..total` around ranges that are pinned to specific workers.
+///
+/// Each `(range, worker_id)` in `affinities` is kept intact and reported with its worker, for
+/// example because that worker already caches the data. The unassigned gaps between pinned
+/// ranges are even-split with [`even_split`] so that no gap batch exceeds `max_batch_size`, and
+/// are reported with the wildcard worker id [`UNASSIGNED_WORKER`] (`usize::MAX`).
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `affinities` - The pinned ranges and their worker ids, disjoint and within `0..total`.
+/// * `max_batch_size` - The maximum allowed size for each gap batch.
+///
+/// # Returns
+///
+/// A `Result` containing `(worker_id, range)` pairs ordered by range start, covering `0..total`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * Any pinned range is empty, extends past `total`, or overlaps another.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_with_affinity, UNASSIGNED_WORKER};
+///
+/// let batches = split_with_affinity(10, &[(4..6, 7)], 2).unwrap();
+/// assert_eq!(batches, vec![
+///     (UNASSIGNED_WORKER, 0..2),
+///     (UNASSIGNED_WORKER, 2..4),
+///     (7, 4..6),
+///     (UNASSIGNED_WORKER, 6..8),
+///     (UNASSIGNED_WORKER, 8..10),
+/// ]);
+/// ```
+pub fn split_with_affinity(total: usize, affinities: &[(Range<usize>, usize)], max_batch_size: usize) -> Result<Vec<(usize, Range<usize>)>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+    if affinities.iter().any(|(range, _)| range.is_empty() || range.end > total) {
+        return Err(BatchError::InvalidInput(String::from("Pinned ranges must be non-empty and lie within 0..total")));
+    }
+
+    let mut pinned: Vec<&(Range<usize>, usize)> = affinities.iter().collect();
+    pinned.sort_by_key(|(range, _)| range.start);
+    if pinned.windows(2).any(|pair| pair[0].0.end > pair[1].0.start) {
+        return Err(BatchError::InvalidInput(String::from("Pinned ranges must not overlap")));
+    }
+
+    let mut batches = Vec::new();
+    let mut gap = Vec::new();
+    let mut start = 0;
+    for (range, worker) in pinned.into_iter().chain(core::iter::once(&(total..total, UNASSIGNED_WORKER))) {
+        if range.start > start {
+            gap.clear();
+            push_even_ranges(&mut gap, start, range.start, max_batch_size)?;
+            batches.extend(gap.drain(..).map(|r| (UNASSIGNED_WORKER, r)));
+        }
+        if !range.is_empty() {
+            batches.push((*worker, range.clone()));
+        }
+        start = range.end;
+    }
+
+    Ok(batches)
+}
+
+/// Computes keyset-pagination bounds for a sorted list of keys.
+///
+/// The keys are cut into consecutive pages of `page_size` (the last page may be shorter), and
+/// each page is described by its first and last key. A client resumes after a page by querying
+/// `key > last_key` instead of a numeric offset, so pages stay stable when items are inserted
+/// into the dataset between requests. The keys must be unique for this to be unambiguous.
+///
+/// # Arguments
+///
+/// * `sorted_keys` - The keys of the dataset, strictly increasing.
+/// * `page_size` - The number of keys on each page.
+///
+/// # Returns
+///
+/// A `Result` containing the `(first_key, last_key)` bounds of each page, in order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The keys slice is empty.
+/// * The page size is zero.
+/// * The keys are not strictly increasing.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::keyset_pages;
+///
+/// let pages = keyset_pages(&[2, 3, 5, 7, 11], 2).unwrap();
+/// assert_eq!(pages, vec![(2, 3), (5, 7), (11, 11)]);
+/// ```
+pub fn keyset_pages<K: Ord + Clone>(sorted_keys: &[K], page_size: usize) -> Result<Vec<(K, K)>, BatchError> {
+    if sorted_keys.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Keys slice must not be empty")));
+    }
+    if page_size == 0 {
+        return Err(BatchError::InvalidInput(String::from("Page size must be a positive number")));
+    }
+    if sorted_keys.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(BatchError::InvalidInput(String::from("Keys must be strictly increasing")));
+    }
+
+    Ok(sorted_keys
+        .chunks(page_size)
+        .map(|page| (page[0].clone(), page[page.len() - 1].clone()))
+        .collect())
+}
+
+/// Splits fixed-width records into batches described by byte offsets.
+///
+/// The record count is split with [`even_split`], and each batch is converted to the
+/// `(byte_start, byte_len)` it occupies in the underlying file or buffer by multiplying by
+/// `record_bytes`. The byte ranges are contiguous and cover `0..total_records * record_bytes`,
+/// so they can be passed directly to `seek`/`read` by parallel readers.
+///
+/// # Arguments
+///
+/// * `total_records` - The total number of records.
+/// * `record_bytes` - The size of a single record in bytes.
+/// * `max_batch_records` - The maximum number of records in each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(byte_start, byte_len)` pairs in batch order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total_records is zero.
+/// * The record_bytes is zero.
+/// * The max_batch_records is zero.
+/// * The total byte length overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::record_offsets;
+///
+/// let offsets = record_offsets(20, 16, 10).unwrap();
+/// assert_eq!(offsets, vec![(0, 160), (160, 160)]);
+/// ```
+pub fn record_offsets(total_records: usize, record_bytes: usize, max_batch_records: usize) -> Result<Vec<(usize, usize)>, BatchError> {
+    if record_bytes == 0 {
+        return Err(BatchError::InvalidInput(String::from("Record size must be a positive number")));
+    }
+    if total_records.checked_mul(record_bytes).is_none() {
+        return Err(BatchError::Overflow);
+    }
+    let (_, batch_sizes) = even_split(total_records, max_batch_records)?;
+
+    let mut byte_start = 0;
+    let mut offsets = Vec::with_capacity(batch_sizes.len());
+    for size in batch_sizes {
+        let byte_len = size.get() * record_bytes;
+        offsets.push((byte_start, byte_len));
+        byte_start += byte_len;
+    }
+
+    Ok(offsets)
+}
+
+/// Returns how many contiguous batches greedy packing needs when no batch may cost more than
+/// `cap`. Every single cost must be at most `cap`.
+fn batches_needed(costs: &[usize], cap: u128) -> usize {
+    let mut batches = 1;
+    let mut current = 0u128;
+    for &cost in costs {
+        if current + cost as u128 > cap {
+            batches += 1;
+            current = 0;
+        }
+        current += cost as u128;
+    }
+    batches
+}
+
+/// Splits items with individual costs into contiguous batches of roughly equal total cost.
+///
+/// This is the linear partition problem: among all ways to cut `0..costs.len()` into
+/// `num_batches` non-empty contiguous ranges, it finds one that minimizes the cost of the most
+/// expensive range. The smallest feasible maximum is found by binary search over the possible
+/// answers, checking each candidate with a greedy packing, and the ranges are then cut greedily
+/// under that maximum while leaving at least one item for every later range.
+///
+/// # Arguments
+///
+/// * `costs` - The processing cost of each item, in order.
+/// * `num_batches` - The number of ranges to produce.
+///
+/// # Returns
+///
+/// A `Result` containing `num_batches` index ranges, in order, covering `0..costs.len()`.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The costs slice is empty.
+/// * The num_batches is zero.
+/// * There are fewer items than batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_cost;
+///
+/// let ranges = split_by_cost(&[2, 2, 2, 2, 4, 4], 3).unwrap();
+/// assert_eq!(ranges, vec![0..3, 3..5, 5..6]);
+/// ```
+pub fn split_by_cost(costs: &[usize], num_batches: usize) -> Result<Vec<Range<usize>>, BatchError> {
+    if costs.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Costs must not be empty")));
+    }
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if costs.len() < num_batches {
+        return Err(BatchError::InsufficientTotal { total: costs.len(), min_required: num_batches });
+    }
+
+    let mut low = costs.iter().copied().max().unwrap() as u128;
+    let mut high: u128 = costs.iter().map(|&cost| cost as u128).sum();
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if batches_needed(costs, mid) <= num_batches {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    let mut ranges = Vec::with_capacity(num_batches);
+    let mut start = 0;
+    let mut current = 0u128;
+    for (i, &cost) in costs.iter().enumerate() {
+        let batches_left = num_batches - ranges.len();
+        let must_cut = costs.len() - i == batches_left - 1;
+        if i > start && (current + cost as u128 > low || must_cut) {
+            ranges.push(start..i);
+            start = i;
+            current = 0;
+        }
+        current += cost as u128;
+    }
+    ranges.push(start..costs.len());
+
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_with_fixed_boundaries() {
+        let ranges = split_with_fixed_boundaries(30, &[4, 20], 5).unwrap();
+        assert_eq!(ranges, vec![0..4, 4..8, 8..12, 12..16, 16..20, 20..25, 25..30]);
+        assert_eq!(split_with_fixed_boundaries(10, &[], 5).unwrap(), vec![0..5, 5..10]);
+        assert_eq!(split_with_fixed_boundaries(10, &[0, 5], 10).unwrap(), vec![0..5, 5..10]);
+    }
+
+    #[test]
+    fn test_split_with_fixed_boundaries_covers_total() {
+        let ranges = split_with_fixed_boundaries(100, &[7, 13, 64], 9).unwrap();
+        assert_eq!(ranges.first().unwrap().start, 0);
+        assert_eq!(ranges.last().unwrap().end, 100);
+        assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+        assert!(ranges.iter().all(|r| !r.is_empty() && r.len() <= 9));
+        for boundary in [7, 13, 64] {
+            assert!(ranges.iter().any(|r| r.start == boundary));
+        }
+    }
+
+    #[test]
+    fn test_split_with_fixed_boundaries_errors() {
+        assert!(split_with_fixed_boundaries(0, &[], 5).is_err());
+        assert!(split_with_fixed_boundaries(10, &[], 0).is_err());
+        assert!(split_with_fixed_boundaries(10, &[5, 5], 5).is_err());
+        assert!(split_with_fixed_boundaries(10, &[6, 3], 5).is_err());
+        assert!(split_with_fixed_boundaries(10, &[10], 5).is_err());
+    }
+
+    #[test]
+    fn test_split_to_match_prefix() {
+        let batch_sizes = split_to_match_prefix(100, &[10, 25, 60, 100]).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![10, 15, 35, 40]);
+        assert_eq!(split_to_match_prefix(5, &[5]), Ok(vec![NonZeroUsize::new(5).unwrap()]));
+    }
+
+    #[test]
+    fn test_split_to_match_prefix_errors() {
+        assert!(split_to_match_prefix(0, &[0]).is_err());
+        assert!(split_to_match_prefix(10, &[]).is_err());
+        assert!(split_to_match_prefix(10, &[3, 7, 9]).is_err());
+        assert!(split_to_match_prefix(10, &[3, 7, 11]).is_err());
+        assert!(split_to_match_prefix(10, &[3, 3, 10]).is_err());
+        assert!(split_to_match_prefix(10, &[7, 3, 10]).is_err());
+        assert!(split_to_match_prefix(10, &[0, 5, 10]).is_err());
+    }
+
+    #[test]
+    fn test_split_with_affinity() {
+        let batches = split_with_affinity(20, &[(14..16, 1), (4..6, 0)], 4).unwrap();
+        assert_eq!(batches, vec![
+            (UNASSIGNED_WORKER, 0..4),
+            (0, 4..6),
+            (UNASSIGNED_WORKER, 6..10),
+            (UNASSIGNED_WORKER, 10..14),
+            (1, 14..16),
+            (UNASSIGNED_WORKER, 16..20),
+        ]);
+    }
+
+    #[test]
+    fn test_split_with_affinity_covers_total() {
+        let batches = split_with_affinity(100, &[(0..10, 2), (40..90, 5)], 8).unwrap();
+        assert_eq!(batches.first().unwrap(), &(2, 0..10));
+        assert!(batches.contains(&(5, 40..90)));
+        assert_eq!(batches.last().unwrap().1.end, 100);
+        assert!(batches.windows(2).all(|pair| pair[0].1.end == pair[1].1.start));
+        assert!(batches.iter().filter(|(w, _)| *w == UNASSIGNED_WORKER).all(|(_, r)| r.len() <= 8));
+    }
+
+    #[test]
+    fn test_split_with_affinity_errors() {
+        assert!(split_with_affinity(0, &[], 4).is_err());
+        assert!(split_with_affinity(10, &[], 0).is_err());
+        assert!(split_with_affinity(10, &[(2..2, 0)], 4).is_err());
+        assert!(split_with_affinity(10, &[(8..11, 0)], 4).is_err());
+        assert!(split_with_affinity(10, &[(2..5, 0), (4..6, 1)], 4).is_err());
+    }
+
+    #[test]
+    fn test_keyset_pages() {
+        let keys: Vec<u32> = (0..25).map(|i| i * 3 + 1).collect();
+        let pages = keyset_pages(&keys, 10).unwrap();
+        assert_eq!(pages, vec![(1, 28), (31, 58), (61, 73)]);
+
+        let mut covered = Vec::new();
+        let mut after = None;
+        for &(first, last) in &pages {
+            let page: Vec<u32> = keys.iter().copied().filter(|&k| after.is_none_or(|a| k > a) && k <= last).collect();
+            assert_eq!(page.first(), Some(&first));
+            covered.extend(page);
+            after = Some(last);
+        }
+        assert_eq!(covered, keys);
+    }
+
+    #[test]
+    fn test_keyset_pages_errors() {
+        assert!(keyset_pages::<u32>(&[], 10).is_err());
+        assert!(keyset_pages(&[1, 2], 0).is_err());
+        assert!(keyset_pages(&[1, 3, 2], 2).is_err());
+        assert!(keyset_pages(&["a", "a"], 2).is_err());
+    }
+
+    #[test]
+    fn test_record_offsets_contiguous() {
+        let offsets = record_offsets(50, 12, 8).unwrap();
+        let (_, batch_sizes) = even_split(50, 8).unwrap();
+        assert_eq!(offsets.len(), batch_sizes.len());
+        let mut expected_start = 0;
+        for (&(byte_start, byte_len), size) in offsets.iter().zip(&batch_sizes) {
+            assert_eq!(byte_start, expected_start);
+            assert_eq!(byte_len, size.get() * 12);
+            expected_start += byte_len;
+        }
+        assert_eq!(expected_start, 50 * 12);
+    }
+
+    #[test]
+    fn test_record_offsets_errors() {
+        assert!(record_offsets(0, 12, 8).is_err());
+        assert!(record_offsets(10, 0, 8).is_err());
+        assert!(record_offsets(10, 12, 0).is_err());
+        assert!(record_offsets(usize::MAX, 2, 8).is_err());
+    }
+
+    #[test]
+    fn test_split_offsets_contiguous() {
+        for (total, num_batches) in [(10, 3), (103, 10), (7, 7), (1, 1)] {
+            let ranges = split_offsets(total, num_batches).unwrap();
+            assert_eq!(ranges.len(), num_batches);
+            assert_eq!(ranges[0].start, 0);
+            assert_eq!(ranges.last().unwrap().end, total);
+            assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+            let lengths: Vec<usize> = ranges.iter().map(|r| r.len()).collect();
+            let expected: Vec<usize> = split_by_count(total, num_batches).unwrap().iter().map(|b| b.get()).collect();
+            assert_eq!(lengths, expected);
+        }
+        assert_eq!(split_offsets(0, 3), Err(BatchError::ZeroTotal));
+        assert_eq!(split_offsets(10, 0), Err(BatchError::ZeroBatchCount));
+    }
+
+    #[test]
+    fn test_even_split_offsets() {
+        let ranges = even_split_offsets(50, 8).unwrap();
+        assert_eq!(ranges.len(), 10);
+        assert_eq!(ranges.last().unwrap().end, 50);
+        assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+        assert!(ranges.iter().all(|r| r.len() == 5));
+        assert_eq!(even_split_offsets(0, 8), Err(BatchError::ZeroTotal));
+        assert_eq!(even_split_offsets(10, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_split_by_cost_is_optimal() {
+        let costs = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 0, 0];
+        for num_batches in 1..=costs.len() {
+            let ranges = split_by_cost(&costs, num_batches).unwrap();
+            assert_eq!(ranges.len(), num_batches);
+            assert_eq!(ranges[0].start, 0);
+            assert_eq!(ranges[num_batches - 1].end, costs.len());
+            assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+            assert!(ranges.iter().all(|range| !range.is_empty()));
+
+            let largest = ranges.iter().map(|range| costs[range.clone()].iter().sum::<usize>()).max().unwrap();
+            let optimum = (costs.iter().copied().max().unwrap()..).find(|&cap| batches_needed(&costs, cap as u128) <= num_batches).unwrap();
+            assert_eq!(largest, optimum);
+        }
+    }
+
+    #[test]
+    fn test_split_by_cost_edge_cases() {
+        assert_eq!(split_by_cost(&[0, 0, 0], 2).unwrap(), vec![0..2, 2..3]);
+        assert_eq!(split_by_cost(&[usize::MAX, usize::MAX], 1).unwrap(), vec![0..2]);
+        assert_eq!(split_by_cost(&[usize::MAX, usize::MAX], 2).unwrap(), vec![0..1, 1..2]);
+        assert!(matches!(split_by_cost(&[], 2), Err(BatchError::InvalidInput(_))));
+        assert_eq!(split_by_cost(&[1, 2], 0), Err(BatchError::ZeroBatchCount));
+        assert_eq!(split_by_cost(&[1, 2], 3), Err(BatchError::InsufficientTotal { total: 2, min_required: 3 }));
+    }
+
+    #[test]
+    fn test_chunk_boundaries_match_chunks() {
+        let items: Vec<usize> = (0..50).collect();
+        for total in 0..=items.len() {
+            for chunk_size in 1..60 {
+                let ranges = chunk_boundaries(total, chunk_size).unwrap();
+                let chunks: Vec<&[usize]> = items[..total].chunks(chunk_size).collect();
+                assert_eq!(ranges.len(), total.div_ceil(chunk_size));
+                assert_eq!(ranges.iter().map(|range| &items[range.clone()]).collect::<Vec<_>>(), chunks);
+            }
+        }
+        assert_eq!(chunk_boundaries(usize::MAX, usize::MAX).unwrap(), vec![0..usize::MAX]);
+        assert_eq!(chunk_boundaries(10, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+}
@@ -0,0 +1,1117 @@
+//! Strategies that revisit or reshape an existing split.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::{even_split, BatchError};
+use crate::weighted::largest_remainder;
+
+/// Re-splits a changed total only when it has moved far enough from the previous one.
+///
+/// In reactive systems the total often fluctuates by small amounts, and re-dispatching work for
+/// every tiny change causes churn. This function compares `new_total` against the total covered
+/// by `previous` and keeps the old split (returning `None`) while the relative change stays below
+/// `threshold_pct` percent. Once the change reaches the threshold, a fresh split is computed with
+/// [`even_split`].
+///
+/// # Arguments
+///
+/// * `previous` - The batch sizes currently in use.
+/// * `new_total` - The updated total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch of a fresh split.
+/// * `threshold_pct` - The relative change, in percent of the previous total, that triggers a re-split.
+///
+/// # Returns
+///
+/// A `Result` containing `None` when the previous split should be kept, or `Some` with the new
+/// batch sizes otherwise.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The threshold is negative or NaN.
+/// * A fresh split is required and `even_split` rejects its arguments.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::resplit_with_hysteresis;
+/// use std::num::NonZeroUsize;
+///
+/// let previous = vec![NonZeroUsize::new(10).unwrap(); 10];
+/// assert_eq!(resplit_with_hysteresis(&previous, 101, 10, 5.0), Ok(None));
+/// assert!(resplit_with_hysteresis(&previous, 110, 10, 5.0).unwrap().is_some());
+/// ```
+pub fn resplit_with_hysteresis(previous: &[NonZeroUsize], new_total: usize, max_batch_size: usize, threshold_pct: f64) -> Result<Option<Vec<NonZeroUsize>>, BatchError> {
+    if threshold_pct.is_nan() || threshold_pct < 0.0 {
+        return Err(BatchError::InvalidInput(String::from("Threshold percentage must be a non-negative number")));
+    }
+
+    let old_total: usize = previous.iter().map(|b| b.get()).sum();
+    if old_total > 0 {
+        let change_pct = old_total.abs_diff(new_total) as f64 * 100.0 / old_total as f64;
+        if change_pct < threshold_pct {
+            return Ok(None);
+        }
+    }
+
+    even_split(new_total, max_batch_size).map(|(_, batch_sizes)| Some(batch_sizes))
+}
+
+/// Splits several queue totals into capped batches and flags queues with tiny tails.
+///
+/// Each total is cut into full batches of `max_batch_size` followed by one final partial batch
+/// holding whatever is left. When that final batch is smaller than a quarter of
+/// `max_batch_size`, the queue is flagged so the caller can consolidate the undersized tails
+/// across queues instead of dispatching many wasteful tiny batches.
+///
+/// The coalescing report is a vector of flags parallel to `totals`: `flags[i]` is `true` when
+/// queue `i` ends in a tiny tail. Queues that divide evenly never have a tail and are never
+/// flagged.
+///
+/// # Arguments
+///
+/// * `totals` - The total number of items in each queue.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. A vector with the batch sizes of each queue, in the order of `totals`.
+/// 2. A vector of flags marking the queues whose final batch is a tiny tail.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The totals slice is empty.
+/// * Any total is zero.
+/// * The max_batch_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_many_coalesced;
+///
+/// let (batches, tiny_tails) = split_many_coalesced(&[17, 24, 30], 8).unwrap();
+/// assert_eq!(batches[0].len(), 3);
+/// assert_eq!(tiny_tails, vec![true, false, false]);
+/// ```
+pub fn split_many_coalesced(totals: &[usize], max_batch_size: usize) -> Result<(Vec<Vec<NonZeroUsize>>, Vec<bool>), BatchError> {
+    if totals.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Totals slice must not be empty")));
+    }
+    if totals.contains(&0) {
+        return Err(BatchError::InvalidInput(String::from("All totals must be positive numbers")));
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    // `tail < max_batch_size / 4` in exact arithmetic, without truncating the quarter.
+    let tiny_threshold = max_batch_size.div_ceil(4);
+    let mut batches = Vec::with_capacity(totals.len());
+    let mut tiny_tails = Vec::with_capacity(totals.len());
+
+    for &total in totals {
+        let full_batches = total / max_batch_size;
+        let tail = total % max_batch_size;
+
+        let mut queue = vec![NonZeroUsize::new(max_batch_size).unwrap(); full_batches];
+        queue.extend(NonZeroUsize::new(tail));
+        batches.push(queue);
+        tiny_tails.push(tail > 0 && tail < tiny_threshold);
+    }
+
+    Ok((batches, tiny_tails))
+}
+
+/// Reorders batches smallest-first and places the flushes that keep buffered output bounded.
+///
+/// Batches are assumed to accumulate in a buffer that is flushed whenever the next batch would
+/// push the buffered size past `flush_threshold`. The batches are emitted in ascending order
+/// (equal sizes keep their original relative order) and a flush is placed before every batch
+/// that would overflow the buffer, so each run of batches between two flushes adds up to at most
+/// `flush_threshold`.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to reorder.
+/// * `flush_threshold` - The maximum buffered size allowed before a flush.
+///
+/// # Returns
+///
+/// A `Result` containing a tuple with:
+/// 1. The reordered batch sizes.
+/// 2. The flush positions: each is the index of the batch that a flush precedes, in increasing
+///    order.
+///
+/// # Errors
+///
+/// Returns an error if any single batch exceeds `flush_threshold`, since it could never fit in
+/// the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::order_for_memory_peak;
+/// use std::num::NonZeroUsize;
+///
+/// let batches: Vec<NonZeroUsize> = [5, 1, 3].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// let (ordered, flushes) = order_for_memory_peak(batches, 6).unwrap();
+/// assert_eq!(ordered.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![1, 3, 5]);
+/// assert_eq!(flushes, vec![2]);
+/// ```
+pub fn order_for_memory_peak(mut batches: Vec<NonZeroUsize>, flush_threshold: usize) -> Result<(Vec<NonZeroUsize>, Vec<usize>), BatchError> {
+    if batches.iter().any(|b| b.get() > flush_threshold) {
+        return Err(BatchError::ImpossibleConstraint(String::from("No batch may exceed the flush threshold")));
+    }
+
+    batches.sort();
+    let mut flushes = Vec::new();
+    let mut buffered = 0;
+    for (i, batch) in batches.iter().enumerate() {
+        // Each batch fits on its own, so the buffer only overflows when it already holds some.
+        if batch.get() > flush_threshold - buffered {
+            flushes.push(i);
+            buffered = 0;
+        }
+        buffered += batch.get();
+    }
+
+    Ok((batches, flushes))
+}
+
+/// Merges under-utilized batches into their neighbors.
+///
+/// A batch is under-utilized when its size is below `min_utilization * target_size`. While such
+/// a batch exists and more than one batch remains, the first under-utilized batch is merged into
+/// its smaller adjacent neighbor (the left one on a tie). The order of the remaining batches and
+/// the total are preserved. Merges may produce batches larger than `target_size`.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to compact.
+/// * `target_size` - The batch size considered fully utilized.
+/// * `min_utilization` - The minimum fraction of `target_size` each batch should reach.
+///
+/// # Returns
+///
+/// A `Result` containing the compacted batch sizes.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The target_size is zero.
+/// * The min_utilization is not in `(0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::compact_under_utilized;
+/// use std::num::NonZeroUsize;
+///
+/// let batches: Vec<NonZeroUsize> = [10, 2, 10, 1].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// let compacted = compact_under_utilized(batches, 10, 0.5).unwrap();
+/// assert_eq!(compacted.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![12, 11]);
+/// ```
+pub fn compact_under_utilized(mut batches: Vec<NonZeroUsize>, target_size: usize, min_utilization: f64) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if target_size == 0 {
+        return Err(BatchError::InvalidInput(String::from("Target size must be a positive number")));
+    }
+    if !(min_utilization > 0.0 && min_utilization <= 1.0) {
+        return Err(BatchError::InvalidInput(String::from("Minimum utilization must be in (0, 1]")));
+    }
+
+    let floor = min_utilization * target_size as f64;
+    while batches.len() > 1 {
+        let Some(index) = batches.iter().position(|b| (b.get() as f64) < floor) else {
+            break;
+        };
+        let neighbor = match (index.checked_sub(1), batches.get(index + 1)) {
+            (Some(left), Some(right)) if *right < batches[left] => index + 1,
+            (Some(left), _) => left,
+            (None, _) => index + 1,
+        };
+        let merged = batches.remove(index);
+        let neighbor = if neighbor > index { neighbor - 1 } else { neighbor };
+        batches[neighbor] = batches[neighbor].saturating_add(merged.get());
+    }
+
+    Ok(batches)
+}
+
+/// Re-splits a new total across workers while bounding each worker's change from a prior epoch.
+///
+/// Worker `i` may receive between `previous_loads[i] - max_drift` (but at least one) and
+/// `previous_loads[i] + max_drift` items. Within those bounds the loads are water-filled toward
+/// the even split of `new_total`: every worker is clamped to a common level, and the units left
+/// over below the next level go to the lowest-indexed workers that can still grow. When the
+/// bounds are wide enough the result equals `split_by_count(new_total, previous_loads.len())`;
+/// otherwise the imbalance is corrected as far as the drift budget allows.
+///
+/// # Arguments
+///
+/// * `previous_loads` - The load of each worker in the previous epoch.
+/// * `new_total` - The total to distribute in this epoch.
+/// * `max_drift` - The maximum change of any worker's load between epochs.
+///
+/// # Returns
+///
+/// A `Result` containing one batch size per worker.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The previous_loads slice is empty.
+/// * The new_total cannot be reached without some worker drifting by more than max_drift.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::rebalance_across_epochs;
+///
+/// let loads = rebalance_across_epochs(&[100, 10, 10, 10], 130, 20).unwrap();
+/// assert_eq!(loads.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![80, 17, 17, 16]);
+/// ```
+pub fn rebalance_across_epochs(previous_loads: &[usize], new_total: usize, max_drift: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if previous_loads.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Previous loads must not be empty")));
+    }
+
+    let bounds: Vec<(usize, usize)> = previous_loads
+        .iter()
+        .map(|&load| (load.saturating_sub(max_drift).max(1), load.saturating_add(max_drift).max(1)))
+        .collect();
+    let filled = |level: usize| -> u128 { bounds.iter().map(|&(lo, hi)| level.clamp(lo, hi) as u128).sum() };
+    let target = new_total as u128;
+    if filled(0) > target || filled(usize::MAX) < target {
+        return Err(BatchError::ImpossibleConstraint(String::from("Drift budget cannot reach the new total")));
+    }
+
+    // Find the highest level whose clamped loads do not exceed the new total.
+    let (mut low, mut high) = (0, bounds.iter().map(|&(_, hi)| hi).max().unwrap_or(0));
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if filled(mid) <= target {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    let mut leftover = (target - filled(low)) as usize;
+    let mut loads = Vec::with_capacity(bounds.len());
+    for &(lo, hi) in &bounds {
+        let mut load = low.clamp(lo, hi);
+        if leftover > 0 && lo <= low && low < hi {
+            load += 1;
+            leftover -= 1;
+        }
+        loads.push(NonZeroUsize::new(load).unwrap());
+    }
+
+    Ok(loads)
+}
+
+/// Scales a split down proportionally so that it fits within a quota.
+///
+/// If the batches already sum to at most `quota` they are returned unchanged. Otherwise every
+/// batch keeps one item, and the remaining `quota - batches.len()` items are apportioned in
+/// proportion to each batch's size above one with largest-remainder rounding (ties go to lower
+/// indices). The result sums to exactly `quota`, every batch stays non-zero, and no batch grows.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to trim.
+/// * `quota` - The maximum allowed sum of the batch sizes.
+///
+/// # Returns
+///
+/// A `Result` containing the trimmed batch sizes, in the original order.
+///
+/// # Errors
+///
+/// Returns an error if the sum exceeds `quota` and `quota` is smaller than the number of
+/// batches, since every batch must keep at least one item.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::trim_to_quota;
+/// use std::num::NonZeroUsize;
+///
+/// let batches: Vec<NonZeroUsize> = [50, 30, 20].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// let trimmed = trim_to_quota(batches, 50).unwrap();
+/// assert_eq!(trimmed.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![25, 15, 10]);
+/// ```
+pub fn trim_to_quota(batches: Vec<NonZeroUsize>, quota: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let sum = batches.iter().fold(0usize, |sum, b| sum.saturating_add(b.get()));
+    if sum <= quota {
+        return Ok(batches);
+    }
+    if quota < batches.len() {
+        return Err(BatchError::ImpossibleConstraint(String::from("Quota must allow at least one item per batch")));
+    }
+
+    let excess: Vec<usize> = batches.iter().map(|b| b.get() - 1).collect();
+    let shares = largest_remainder(quota - batches.len(), &excess);
+    Ok(shares.into_iter().map(|share| NonZeroUsize::MIN.saturating_add(share)).collect())
+}
+
+/// Greedily merges adjacent batches while their combined size stays within a limit.
+///
+/// Walking the batches in order, each batch is added to the current merged batch if the result
+/// does not exceed `target_max`; otherwise the current batch is closed and a new one starts.
+/// The order and the total are preserved. A single batch already larger than `target_max` is
+/// kept as it is.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to merge.
+/// * `target_max` - The largest size a merged batch may reach.
+///
+/// # Returns
+///
+/// A `Result` containing the merged batch sizes.
+///
+/// # Errors
+///
+/// Returns an error if the target_max is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::merge_batches;
+/// use std::num::NonZeroUsize;
+///
+/// let batches = vec![NonZeroUsize::new(3).unwrap(); 4];
+/// let merged = merge_batches(&batches, 7).unwrap();
+/// assert_eq!(merged, vec![NonZeroUsize::new(6).unwrap(); 2]);
+/// ```
+pub fn merge_batches(batches: &[NonZeroUsize], target_max: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if target_max == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let mut merged: Vec<NonZeroUsize> = Vec::new();
+    for &batch in batches {
+        match merged.last_mut() {
+            Some(last) if last.get().checked_add(batch.get()).is_some_and(|sum| sum <= target_max) => {
+                *last = last.saturating_add(batch.get());
+            }
+            _ => merged.push(batch),
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Merges adjacent batches into exactly `target_count` groups of sizes as even as possible.
+///
+/// The batches are cut into `target_count` contiguous groups, each merged into one batch. Among
+/// all such cuts, the one minimizing the variance of the merged sizes is chosen; since the total
+/// and the count are fixed, this is the cut with the smallest sum of squared sizes. It is found
+/// by dynamic programming over prefix sums in `O(target_count * n^2)` time for `n` batches, and
+/// among equally good cuts the one with the earliest boundaries wins. The order and the total
+/// are preserved.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to merge.
+/// * `target_count` - The number of merged batches to produce.
+///
+/// # Returns
+///
+/// A `Result` containing `target_count` merged batch sizes, in order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The target_count is zero.
+/// * The target_count exceeds the number of batches.
+/// * The sum of the batch sizes overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::merge_to_count;
+/// use std::num::NonZeroUsize;
+///
+/// let batches: Vec<NonZeroUsize> = [4, 1, 1, 2, 3, 3].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// let merged = merge_to_count(&batches, 3).unwrap();
+/// assert_eq!(merged.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![4, 4, 6]);
+/// ```
+pub fn merge_to_count(batches: &[NonZeroUsize], target_count: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if target_count == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if target_count > batches.len() {
+        return Err(BatchError::InvalidInput(format!(
+            "Cannot merge {} batches into {} groups",
+            batches.len(),
+            target_count
+        )));
+    }
+
+    let mut prefix = Vec::with_capacity(batches.len() + 1);
+    prefix.push(0usize);
+    for size in batches {
+        let sum = prefix[prefix.len() - 1].checked_add(size.get()).ok_or(BatchError::Overflow)?;
+        prefix.push(sum);
+    }
+    // The total fits in usize, so any sum of squared group sizes fits in u128.
+    let square = |from: usize, to: usize| {
+        let size = (prefix[to] - prefix[from]) as u128;
+        size * size
+    };
+
+    // cost[i] is the best sum of squares for the first i batches in the current number of groups,
+    // and cuts[j][i] the start of the last group in that best cut.
+    let n = batches.len();
+    let mut cost: Vec<u128> = (0..=n).map(|i| square(0, i)).collect();
+    let mut cuts = vec![vec![0; n + 1]];
+    for groups in 2..=target_count {
+        let mut next = vec![u128::MAX; n + 1];
+        let mut starts = vec![0; n + 1];
+        for i in groups..=n {
+            for (m, &previous) in cost.iter().enumerate().take(i).skip(groups - 1) {
+                let candidate = previous + square(m, i);
+                if candidate < next[i] {
+                    next[i] = candidate;
+                    starts[i] = m;
+                }
+            }
+        }
+        cost = next;
+        cuts.push(starts);
+    }
+
+    let mut merged = vec![NonZeroUsize::MIN; target_count];
+    let mut end = n;
+    for groups in (1..=target_count).rev() {
+        let start = cuts[groups - 1][end];
+        merged[groups - 1] = NonZeroUsize::new(prefix[end] - prefix[start]).expect("every group holds at least one batch");
+        end = start;
+    }
+
+    Ok(merged)
+}
+
+/// Folds batches smaller than a minimum into their neighbours.
+///
+/// Walking the batches in order, a batch below `min` is added to the preceding batch. Leading
+/// batches have no predecessor, so they are accumulated into the following batches until the
+/// accumulated size reaches `min`. The order of the remaining batches and the total are
+/// preserved; if even the whole total stays below `min`, everything collapses into a single
+/// batch. A merged size that would overflow `usize` saturates.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to merge.
+/// * `min` - The smallest size a batch should keep on its own.
+///
+/// # Returns
+///
+/// The merged batch sizes; empty only if `batches` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{merge_below_min, split_with_remainder};
+/// use std::num::NonZeroUsize;
+///
+/// let (_, mut batches, remainder) = split_with_remainder(50, 8).unwrap();
+/// batches.push(NonZeroUsize::new(remainder).unwrap());
+/// let merged = merge_below_min(&batches, 4);
+/// assert_eq!(merged.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 8, 8, 8, 8, 10]);
+/// ```
+pub fn merge_below_min(batches: &[NonZeroUsize], min: usize) -> Vec<NonZeroUsize> {
+    let mut merged: Vec<NonZeroUsize> = Vec::new();
+    let mut leading: Option<NonZeroUsize> = None;
+    for &batch in batches {
+        if merged.is_empty() {
+            let accumulated = leading.map_or(batch, |leading| leading.saturating_add(batch.get()));
+            if accumulated.get() >= min {
+                merged.push(accumulated);
+                leading = None;
+            } else {
+                leading = Some(accumulated);
+            }
+        } else if batch.get() < min {
+            let last = merged.last_mut().expect("merged is not empty");
+            *last = last.saturating_add(batch.get());
+        } else {
+            merged.push(batch);
+        }
+    }
+    merged.extend(leading);
+    merged
+}
+
+/// Redistributes the total of an existing split as evenly as possible over the same batch count.
+///
+/// The result is exactly `split_by_count(total, batches.len())`, where `total` is the sum of the
+/// input: sizes differ by at most one, with the larger batches first. This flattens skew
+/// introduced by appending work to some batches. An empty input yields an empty output.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to rebalance.
+///
+/// # Returns
+///
+/// A vector of the same length as `batches` with the same sum.
+///
+/// # Panics
+///
+/// Panics if the sum of the batch sizes overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::rebalance;
+/// use std::num::NonZeroUsize;
+///
+/// let batches: Vec<NonZeroUsize> = [9, 1, 2].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// let balanced = rebalance(&batches);
+/// assert_eq!(balanced.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![4, 4, 4]);
+/// ```
+pub fn rebalance(batches: &[NonZeroUsize]) -> Vec<NonZeroUsize> {
+    if batches.is_empty() {
+        return Vec::new();
+    }
+
+    let total = batches.iter().map(|b| b.get()).sum();
+    crate::split_by_count(total, batches.len()).expect("every batch holds at least one item")
+}
+
+/// Smooths a split in place until its sizes are within `max_spread` of each other.
+///
+/// Unlike [`rebalance`], which always produces a perfectly even split, this moves items from the
+/// largest batches to the smallest only until `max - min <= max_spread`, so a split that is
+/// already good enough is left alone and as few items as possible are reassigned. A spread of
+/// one cannot be improved by moving whole items, so a `max_spread` of zero stops at one when the
+/// total does not divide evenly. The total and the batch count are preserved.
+///
+/// The result is the one reached by repeatedly moving a single item from the largest batch to
+/// the smallest, so a batch that gives items away never receives any back, but it is computed
+/// directly by water-filling: the sizes are clamped into the cheapest window
+/// `[low, low + max_spread]` that can hold the total, and any surplus or shortfall is settled
+/// with the smallest or largest batches respectively (lower indices first on ties).
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to smooth.
+/// * `max_spread` - The largest acceptable difference between the largest and smallest batch.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::rebalance_within;
+/// use std::num::NonZeroUsize;
+///
+/// let mut batches: Vec<NonZeroUsize> = [10, 4, 6].iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect();
+/// rebalance_within(&mut batches, 2);
+/// assert_eq!(batches.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![8, 6, 6]);
+/// ```
+pub fn rebalance_within(batches: &mut [NonZeroUsize], max_spread: usize) {
+    rebalance_within_by(batches, max_spread, |_, _, _| {});
+}
+
+/// Runs `rebalance_within`, reporting each transfer as `(from, to, items)` batch indices.
+fn rebalance_within_by(batches: &mut [NonZeroUsize], max_spread: usize, mut on_move: impl FnMut(usize, usize, usize)) {
+    let (Some(max), Some(min)) = (batches.iter().max(), batches.iter().min()) else {
+        return;
+    };
+    // A spread of one cannot be reduced by moving whole items, only swapped around.
+    let spread = max_spread.max(1);
+    if max.get() - min.get() <= spread {
+        return;
+    }
+
+    let original: Vec<usize> = batches.iter().map(|b| b.get()).collect();
+    let count = original.len() as u128;
+    let total: u128 = original.iter().map(|&b| b as u128).sum();
+    // The items that must leave the batches above `high`, and reach the batches below `low`.
+    let above = |high: usize| original.iter().map(|&b| b.saturating_sub(high) as u128).sum::<u128>();
+    let below = |low: usize| original.iter().map(|&b| low.saturating_sub(b) as u128).sum::<u128>();
+    let moved = |low: usize| above(low.saturating_add(spread)).max(below(low));
+
+    // The window `[low, low + spread]` must be able to hold the total. Within those bounds,
+    // `above` falls and `below` rises as `low` grows, so the cheapest window is where they cross.
+    let first = (total.div_ceil(count).saturating_sub(spread as u128) as usize).max(1);
+    let (mut low, mut last) = (first, (total / count) as usize);
+    while low < last {
+        let mid = low + (last - low) / 2;
+        if below(mid) >= above(mid.saturating_add(spread)) {
+            last = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    if low > first && moved(low - 1) <= moved(low) {
+        low -= 1;
+    }
+    let high = low.saturating_add(spread);
+
+    let mut sizes: Vec<usize> = original.iter().map(|&b| b.clamp(low, high)).collect();
+    let (taken, given) = (above(high), below(low));
+    if taken > given {
+        let receivers: Vec<usize> = (0..sizes.len()).filter(|&i| original[i] <= high).collect();
+        raise_smallest(&mut sizes, &receivers, taken - given);
+    } else if given > taken {
+        let donors: Vec<usize> = (0..sizes.len()).filter(|&i| original[i] >= low).collect();
+        lower_largest(&mut sizes, &donors, given - taken);
+    }
+
+    let mut receivers = (0..sizes.len()).filter(|&i| sizes[i] > original[i]).map(|i| (i, sizes[i] - original[i]));
+    let mut receiver = receivers.next();
+    for from in (0..sizes.len()).filter(|&i| sizes[i] < original[i]) {
+        let mut surplus = original[from] - sizes[from];
+        while surplus > 0 {
+            let (to, needed) = receiver.as_mut().expect("every item given away is received");
+            let items = surplus.min(*needed);
+            on_move(from, *to, items);
+            surplus -= items;
+            *needed -= items;
+            if *needed == 0 {
+                receiver = receivers.next();
+            }
+        }
+    }
+    for (batch, size) in batches.iter_mut().zip(sizes) {
+        *batch = NonZeroUsize::new(size).unwrap();
+    }
+}
+
+/// Adds `units` items to the smallest of the `eligible` sizes, levelling them up from the bottom
+/// (lower indices first among equal sizes).
+fn raise_smallest(sizes: &mut [usize], eligible: &[usize], units: u128) {
+    let needed = |level: usize| eligible.iter().map(|&i| level.saturating_sub(sizes[i]) as u128).sum::<u128>();
+    // Find the highest level the units can lift every eligible size to.
+    let largest = eligible.iter().map(|&i| sizes[i]).max().unwrap_or(0);
+    let (mut level, mut top) = (0, largest.saturating_add(usize::try_from(units).unwrap_or(usize::MAX)));
+    while level < top {
+        let mid = level + (top - level).div_ceil(2);
+        if needed(mid) <= units {
+            level = mid;
+        } else {
+            top = mid - 1;
+        }
+    }
+    let mut leftover = units - needed(level);
+    for &i in eligible {
+        sizes[i] = sizes[i].max(level);
+    }
+    for &i in eligible {
+        if leftover > 0 && sizes[i] == level {
+            sizes[i] += 1;
+            leftover -= 1;
+        }
+    }
+}
+
+/// Removes `units` items from the largest of the `eligible` sizes, levelling them down from the
+/// top (lower indices first among equal sizes).
+fn lower_largest(sizes: &mut [usize], eligible: &[usize], units: u128) {
+    let excess = |level: usize| eligible.iter().map(|&i| sizes[i].saturating_sub(level) as u128).sum::<u128>();
+    // Find the lowest level the units can bring every eligible size down to.
+    let (mut bottom, mut level) = (0, eligible.iter().map(|&i| sizes[i]).max().unwrap_or(0));
+    while bottom < level {
+        let mid = bottom + (level - bottom) / 2;
+        if excess(mid) <= units {
+            level = mid;
+        } else {
+            bottom = mid + 1;
+        }
+    }
+    let mut leftover = units - excess(level);
+    for &i in eligible {
+        sizes[i] = sizes[i].min(level);
+    }
+    for &i in eligible {
+        if leftover > 0 && sizes[i] == level {
+            sizes[i] -= 1;
+            leftover -= 1;
+        }
+    }
+}
+
+/// A splitter that rotates where the remainder lands across successive splits.
+///
+/// [`split_by_count`](crate::split_by_count) always gives the `total % num_batches` extra items to
+/// the first batches, so when many independent totals are split for the same workers, worker 0
+/// is systematically overloaded. `FairSplitter` keeps a rotating offset: each call to
+/// [`split`](FairSplitter::split) places its extra items starting at the current offset (wrapping
+/// around) and then advances the offset by the number of extra items placed. Over many calls
+/// the extra items spread evenly across all batch indices.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::FairSplitter;
+///
+/// let mut splitter = FairSplitter::new();
+/// let sizes = |batches: Vec<std::num::NonZeroUsize>| batches.iter().map(|b| b.get()).collect::<Vec<_>>();
+/// assert_eq!(sizes(splitter.split(10, 4).unwrap()), vec![3, 3, 2, 2]);
+/// assert_eq!(sizes(splitter.split(10, 4).unwrap()), vec![2, 2, 3, 3]);
+/// assert_eq!(sizes(splitter.split(9, 4).unwrap()), vec![3, 2, 2, 2]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FairSplitter {
+    offset: usize,
+}
+
+impl FairSplitter {
+    /// Creates a splitter whose first remainder starts at batch index 0.
+    pub fn new() -> Self {
+        FairSplitter::default()
+    }
+
+    /// Splits a total into `num_batches` balanced batches, placing the remainder at the current
+    /// rotating offset and advancing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * The total is zero.
+    /// * The num_batches is zero.
+    /// * The total is smaller than num_batches.
+    pub fn split(&mut self, total: usize, num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+        if total == 0 {
+            return Err(BatchError::ZeroTotal);
+        }
+        if num_batches == 0 {
+            return Err(BatchError::ZeroBatchCount);
+        }
+        let base_size = NonZeroUsize::new(total / num_batches)
+            .ok_or(BatchError::InsufficientTotal { total, min_required: num_batches })?;
+        let remainder = total % num_batches;
+
+        let start = self.offset % num_batches;
+        let mut batch_sizes = vec![base_size; num_batches];
+        for k in 0..remainder {
+            let index = (start + k) % num_batches;
+            batch_sizes[index] = base_size.saturating_add(1);
+        }
+        self.offset = (start + remainder) % num_batches;
+
+        Ok(batch_sizes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_batches(sizes: &[usize]) -> Vec<NonZeroUsize> {
+        sizes.iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect()
+    }
+
+    fn sizes(batches: &[NonZeroUsize]) -> Vec<usize> {
+        batches.iter().map(|b| b.get()).collect()
+    }
+
+    #[test]
+    fn test_merge_below_min() {
+        assert_eq!(merge_below_min(&to_batches(&[1, 1, 5, 2, 6]), 3), to_batches(&[9, 6]));
+        assert_eq!(merge_below_min(&to_batches(&[1, 1, 1]), 5), to_batches(&[3]));
+        assert_eq!(merge_below_min(&to_batches(&[4, 1, 4]), 0), to_batches(&[4, 1, 4]));
+        assert_eq!(merge_below_min(&to_batches(&[2, 9]), 3), to_batches(&[11]));
+        assert!(merge_below_min(&[], 3).is_empty());
+        for min in 0..12 {
+            let batches = to_batches(&[3, 1, 7, 2, 2, 10, 1, 5]);
+            let merged = merge_below_min(&batches, min);
+            assert_eq!(merged.iter().map(|b| b.get()).sum::<usize>(), 31);
+            assert!(merged.len() == 1 || merged.iter().all(|b| b.get() >= min));
+        }
+    }
+
+    #[test]
+    fn test_resplit_with_hysteresis() {
+        let previous = vec![NonZeroUsize::new(10).unwrap(); 10];
+        assert_eq!(resplit_with_hysteresis(&previous, 101, 10, 5.0), Ok(None));
+        assert_eq!(resplit_with_hysteresis(&previous, 99, 10, 5.0), Ok(None));
+        assert_eq!(resplit_with_hysteresis(&previous, 110, 10, 5.0), Ok(Some(even_split(110, 10).unwrap().1)));
+        assert_eq!(resplit_with_hysteresis(&previous, 90, 10, 5.0), Ok(Some(even_split(90, 10).unwrap().1)));
+    }
+
+    #[test]
+    fn test_resplit_with_hysteresis_zero_threshold() {
+        let previous = vec![NonZeroUsize::new(10).unwrap(); 10];
+        assert_eq!(resplit_with_hysteresis(&previous, 100, 10, 0.0), Ok(Some(previous.clone())));
+        assert!(resplit_with_hysteresis(&[], 10, 10, 5.0).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_resplit_with_hysteresis_errors() {
+        let previous = vec![NonZeroUsize::new(10).unwrap(); 10];
+        assert!(resplit_with_hysteresis(&previous, 110, 10, -1.0).is_err());
+        assert!(resplit_with_hysteresis(&previous, 110, 10, f64::NAN).is_err());
+        assert!(resplit_with_hysteresis(&previous, 0, 10, 5.0).is_err());
+    }
+
+    #[test]
+    fn test_split_many_coalesced() {
+        let (batches, tiny_tails) = split_many_coalesced(&[3, 17, 41, 64, 70], 16).unwrap();
+        assert_eq!(tiny_tails, vec![true, true, false, false, false]);
+        assert_eq!(batches[1], vec![NonZeroUsize::new(16).unwrap(), NonZeroUsize::new(1).unwrap()]);
+        assert_eq!(batches[3], vec![NonZeroUsize::new(16).unwrap(); 4]);
+        for (queue, &total) in batches.iter().zip(&[3, 17, 41, 64, 70]) {
+            assert_eq!(queue.iter().map(|b| b.get()).sum::<usize>(), total);
+        }
+
+        // A quarter of 10 is 2.5, so a tail of 2 is tiny and a tail of 3 is not.
+        assert_eq!(split_many_coalesced(&[12, 13, 21], 10).unwrap().1, vec![true, false, true]);
+        // A quarter of 3 is below one item, so no tail is tiny.
+        assert_eq!(split_many_coalesced(&[4, 5, 6], 3).unwrap().1, vec![false, false, false]);
+    }
+
+    #[test]
+    fn test_split_many_coalesced_errors() {
+        assert!(split_many_coalesced(&[], 8).is_err());
+        assert!(split_many_coalesced(&[10, 0], 8).is_err());
+        assert!(split_many_coalesced(&[10], 0).is_err());
+    }
+
+    #[test]
+    fn test_order_for_memory_peak() {
+        let sizes = [7, 2, 9, 4, 1, 8, 3];
+        let batches = to_batches(&sizes);
+        let (ordered, flushes) = order_for_memory_peak(batches.clone(), 10).unwrap();
+        assert_eq!(flushes, vec![4, 5, 6]);
+
+        let bounds: Vec<usize> = core::iter::once(0).chain(flushes.iter().copied()).chain([ordered.len()]).collect();
+        for group in bounds.windows(2) {
+            assert!(group[0] < group[1]);
+            assert!(ordered[group[0]..group[1]].iter().map(|b| b.get()).sum::<usize>() <= 10);
+        }
+
+        let mut expected = batches;
+        expected.sort();
+        assert_eq!(ordered, expected);
+    }
+
+    #[test]
+    fn test_order_for_memory_peak_errors() {
+        let batches = vec![NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(11).unwrap()];
+        assert!(order_for_memory_peak(batches, 10).is_err());
+        assert_eq!(order_for_memory_peak(vec![], 0), Ok((vec![], vec![])));
+    }
+
+    #[test]
+    fn test_fair_splitter_balances_extras() {
+        let mut splitter = FairSplitter::new();
+        let mut fair_extras = [0usize; 8];
+        let mut plain_extras = [0usize; 8];
+        for total in (0..200).map(|i| 80 + i % 3) {
+            for (i, size) in splitter.split(total, 8).unwrap().iter().enumerate() {
+                fair_extras[i] += size.get() - 10;
+            }
+            for (i, size) in crate::split_by_count(total, 8).unwrap().iter().enumerate() {
+                plain_extras[i] += size.get() - 10;
+            }
+        }
+        assert_eq!(fair_extras.iter().sum::<usize>(), plain_extras.iter().sum::<usize>());
+        let spread = |extras: &[usize]| extras.iter().max().unwrap() - extras.iter().min().unwrap();
+        assert!(spread(&fair_extras) <= 1);
+        assert!(spread(&plain_extras) > 100);
+    }
+
+    #[test]
+    fn test_fair_splitter_changing_counts() {
+        let mut splitter = FairSplitter::new();
+        for (total, num_batches) in [(7, 3), (11, 5), (4, 4), (9, 2)] {
+            let batch_sizes = splitter.split(total, num_batches).unwrap();
+            assert_eq!(batch_sizes.len(), num_batches);
+            assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+        }
+        assert!(splitter.split(0, 3).is_err());
+        assert!(splitter.split(3, 0).is_err());
+        assert!(splitter.split(3, 4).is_err());
+    }
+
+    #[test]
+    fn test_compact_under_utilized() {
+        assert_eq!(sizes(&compact_under_utilized(to_batches(&[10, 2, 10, 1]), 10, 0.5).unwrap()), vec![12, 11]);
+        assert_eq!(sizes(&compact_under_utilized(to_batches(&[1, 8, 2, 9]), 10, 0.5).unwrap()), vec![11, 9]);
+        assert_eq!(sizes(&compact_under_utilized(to_batches(&[1, 1, 1]), 10, 1.0).unwrap()), vec![3]);
+        assert_eq!(sizes(&compact_under_utilized(to_batches(&[6, 7]), 10, 0.5).unwrap()), vec![6, 7]);
+        assert!(compact_under_utilized(vec![], 10, 0.5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compact_under_utilized_errors() {
+        let batches = vec![NonZeroUsize::new(3).unwrap()];
+        assert!(compact_under_utilized(batches.clone(), 0, 0.5).is_err());
+        assert!(compact_under_utilized(batches.clone(), 10, 0.0).is_err());
+        assert!(compact_under_utilized(batches.clone(), 10, 1.5).is_err());
+        assert!(compact_under_utilized(batches, 10, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_rebalance_across_epochs_partial() {
+        let previous = [100, 10, 10, 10];
+        let loads = sizes(&rebalance_across_epochs(&previous, 130, 20).unwrap());
+        assert_eq!(loads, vec![80, 17, 17, 16]);
+        assert_eq!(loads.iter().sum::<usize>(), 130);
+        for (&load, &prev) in loads.iter().zip(&previous) {
+            assert!(load.abs_diff(prev) <= 20);
+        }
+    }
+
+    #[test]
+    fn test_rebalance_across_epochs_ample_budget() {
+        let loads = rebalance_across_epochs(&[100, 10, 10, 10], 130, 100).unwrap();
+        assert_eq!(loads, crate::split_by_count(130, 4).unwrap());
+        let loads = rebalance_across_epochs(&[5, 5, 5], 17, 10).unwrap();
+        assert_eq!(loads, crate::split_by_count(17, 3).unwrap());
+    }
+
+    #[test]
+    fn test_rebalance_across_epochs_errors() {
+        assert!(rebalance_across_epochs(&[], 10, 5).is_err());
+        assert!(rebalance_across_epochs(&[10, 10], 50, 5).is_err());
+        assert!(rebalance_across_epochs(&[10, 10], 5, 5).is_err());
+        assert!(rebalance_across_epochs(&[10, 10], 0, 100).is_err());
+    }
+
+    #[test]
+    fn test_trim_to_quota_proportional() {
+        assert_eq!(sizes(&trim_to_quota(to_batches(&[50, 30, 20]), 50).unwrap()), vec![25, 15, 10]);
+        assert_eq!(sizes(&trim_to_quota(to_batches(&[50, 30, 20]), 100).unwrap()), vec![50, 30, 20]);
+        assert_eq!(sizes(&trim_to_quota(to_batches(&[50, 30, 20]), 3).unwrap()), vec![1, 1, 1]);
+        let trimmed = sizes(&trim_to_quota(to_batches(&[7, 100, 1, 13]), 37).unwrap());
+        assert_eq!(trimmed.iter().sum::<usize>(), 37);
+        assert!(trimmed.iter().zip([7, 100, 1, 13]).all(|(&t, s)| t >= 1 && t <= s));
+
+        let large = [usize::MAX / 2, usize::MAX / 2, 3];
+        let trimmed = sizes(&trim_to_quota(to_batches(&large), usize::MAX - 5).unwrap());
+        assert_eq!(trimmed.iter().map(|&t| t as u128).sum::<u128>(), (usize::MAX - 5) as u128);
+        assert!(trimmed.iter().zip(large).all(|(&t, s)| t >= 1 && t <= s));
+    }
+
+    #[test]
+    fn test_trim_to_quota_errors() {
+        let batches = vec![NonZeroUsize::new(5).unwrap(); 3];
+        assert!(trim_to_quota(batches, 2).is_err());
+    }
+
+    #[test]
+    fn test_merge_to_count() {
+        assert_eq!(sizes(&merge_to_count(&to_batches(&[3, 3, 3, 3]), 2).unwrap()), vec![6, 6]);
+        assert_eq!(sizes(&merge_to_count(&to_batches(&[9, 1, 1, 1]), 2).unwrap()), vec![9, 3]);
+        assert_eq!(sizes(&merge_to_count(&to_batches(&[1, 2, 3]), 3).unwrap()), vec![1, 2, 3]);
+        assert_eq!(sizes(&merge_to_count(&to_batches(&[1, 2, 3]), 1).unwrap()), vec![6]);
+
+        // Compare against every possible pair of cut positions.
+        let batches = to_batches(&[1, 7, 2, 2, 3, 8, 1, 1, 1]);
+        let merged = sizes(&merge_to_count(&batches, 3).unwrap());
+        assert_eq!(merged.iter().sum::<usize>(), 26);
+        let squares = |parts: &[usize]| parts.iter().map(|&p| p * p).sum::<usize>();
+        let prefix: Vec<usize> = core::iter::once(0).chain(batches.iter().scan(0, |sum, b| {
+            *sum += b.get();
+            Some(*sum)
+        })).collect();
+        let best = (1..batches.len())
+            .flat_map(|a| (a + 1..batches.len()).map(move |b| (a, b)))
+            .map(|(a, b)| squares(&[prefix[a], prefix[b] - prefix[a], 26 - prefix[b]]))
+            .min()
+            .unwrap();
+        assert_eq!(squares(&merged), best);
+    }
+
+    #[test]
+    fn test_merge_to_count_errors() {
+        let batches = vec![NonZeroUsize::MIN; 3];
+        assert_eq!(merge_to_count(&batches, 0), Err(BatchError::ZeroBatchCount));
+        assert!(matches!(merge_to_count(&batches, 4), Err(BatchError::InvalidInput(_))));
+        assert_eq!(merge_to_count(&[NonZeroUsize::MAX, NonZeroUsize::MIN], 1), Err(BatchError::Overflow));
+    }
+
+    #[test]
+    fn test_merge_batches() {
+        assert_eq!(sizes(&merge_batches(&to_batches(&[3, 3, 3, 3]), 7).unwrap()), vec![6, 6]);
+        assert_eq!(sizes(&merge_batches(&to_batches(&[2, 9, 1, 1, 4]), 6).unwrap()), vec![2, 9, 6]);
+        assert_eq!(sizes(&merge_batches(&to_batches(&[5, 5]), 4).unwrap()), vec![5, 5]);
+        let batches = to_batches(&[1, 7, 2, 2, 3, 8, 1, 1, 1]);
+        for target_max in 1..20 {
+            let merged = merge_batches(&batches, target_max).unwrap();
+            assert_eq!(merged.iter().map(|b| b.get()).sum::<usize>(), 26);
+            assert!(merged.len() <= batches.len());
+        }
+        assert!(merge_batches(&[], 5).unwrap().is_empty());
+        assert_eq!(merge_batches(&batches, 0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_rebalance() {
+        for sizes in [&[9, 1, 2][..], &[1, 1, 1, 20], &[5], &[3, 3, 3], &[100, 1, 1, 1, 1, 1, 1]] {
+            let batches = to_batches(sizes);
+            let balanced = rebalance(&batches);
+            let total: usize = sizes.iter().sum();
+            assert_eq!(balanced.len(), batches.len());
+            assert_eq!(balanced.iter().map(|b| b.get()).sum::<usize>(), total);
+            assert_eq!(balanced, crate::split_by_count(total, sizes.len()).unwrap());
+        }
+        assert!(rebalance(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_rebalance_within() {
+        for sizes in [&[9, 1, 2][..], &[1, 1, 1, 20], &[5], &[3, 3, 3], &[100, 1, 1, 1, 1, 1, 1], &[1_000_000, 1]] {
+            for max_spread in 0..6 {
+                let mut batches = to_batches(sizes);
+                rebalance_within(&mut batches, max_spread);
+                let max = batches.iter().max().unwrap().get();
+                let min = batches.iter().min().unwrap().get();
+                assert!(max - min <= max_spread.max(1));
+                assert_eq!(batches.len(), sizes.len());
+                assert_eq!(batches.iter().map(|b| b.get()).sum::<usize>(), sizes.iter().sum::<usize>());
+            }
+        }
+
+        let mut batches = to_batches(&[7, 5, 6]);
+        rebalance_within(&mut batches, 2);
+        assert_eq!(batches, to_batches(&[7, 5, 6]));
+        rebalance_within(&mut [], 0);
+    }
+
+    #[test]
+    fn test_rebalance_within_donors_never_receive() {
+        for sizes in [&[10, 1, 10][..], &[9, 1, 2], &[1, 1, 1, 20], &[100, 1, 1, 1, 1, 1, 1], &[8, 8, 1, 1, 5]] {
+            for max_spread in 0..6 {
+                let mut batches = to_batches(sizes);
+                let mut moves = Vec::new();
+                rebalance_within_by(&mut batches, max_spread, |from, to, items| moves.push((from, to, items)));
+                assert!(moves.iter().all(|&(from, _, _)| moves.iter().all(|&(_, to, _)| to != from)));
+                let given: usize = sizes.iter().zip(&batches).map(|(&s, b)| s.saturating_sub(b.get())).sum();
+                assert_eq!(moves.iter().map(|&(_, _, items)| items).sum::<usize>(), given);
+            }
+        }
+
+        let mut batches = to_batches(&[10, 1, 10]);
+        let mut moved = 0;
+        rebalance_within_by(&mut batches, 0, |_, _, items| moved += items);
+        assert_eq!(batches, to_batches(&[7, 7, 7]));
+        assert_eq!(moved, 6);
+    }
+
+    #[test]
+    fn test_rebalance_within_large_sizes() {
+        let mut batches = to_batches(&[1 << 40, 1]);
+        rebalance_within(&mut batches, 0);
+        assert_eq!(batches, to_batches(&[(1 << 39) + 1, 1 << 39]));
+
+        let mut batches = to_batches(&[usize::MAX / 2, usize::MAX / 2, 1, 1, 7]);
+        let mut moved = 0;
+        rebalance_within_by(&mut batches, 3, |_, _, items| moved += items);
+        let (max, min) = (batches.iter().max().unwrap().get(), batches.iter().min().unwrap().get());
+        assert!(max - min <= 3);
+        assert_eq!(batches.iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128 - 1 + 9);
+        assert_eq!(moved, 2 * (usize::MAX / 2 - max));
+    }
+}
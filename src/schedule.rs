@@ -0,0 +1,446 @@
+//! Strategies that place batches on a timeline.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+#[cfg(feature = "std")]
+use core::ops::Range;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::{even_split, BatchError};
+
+/// Multiplies a per-item duration by an item count, returning `None` on overflow.
+///
+/// The product is computed in nanoseconds as a `u128`, so counts beyond `u32::MAX` are fine as
+/// long as the result fits in a `Duration`.
+fn scale_duration(per_item: Duration, count: usize) -> Option<Duration> {
+    let nanos = per_item.as_nanos().checked_mul(count as u128)?;
+    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// Lays out an even split as a sequential processing timeline.
+///
+/// The batches are those produced by [`even_split`]. Each batch is processed after the previous
+/// one finishes, so its start offset is the sum of the preceding durations and its duration is
+/// `size * per_item`. The last batch therefore ends at `total * per_item`, and the output can be
+/// fed straight into a Gantt-style visualization.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `per_item` - The time needed to process a single item.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(start_offset, duration)` pairs in batch order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * The per_item duration is zero.
+/// * The schedule's total duration overflows `Duration`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::gantt_schedule;
+/// use std::time::Duration;
+///
+/// let schedule = gantt_schedule(20, 10, Duration::from_millis(5)).unwrap();
+/// assert_eq!(schedule, vec![
+///     (Duration::ZERO, Duration::from_millis(50)),
+///     (Duration::from_millis(50), Duration::from_millis(50)),
+/// ]);
+/// ```
+pub fn gantt_schedule(total: usize, max_batch_size: usize, per_item: Duration) -> Result<Vec<(Duration, Duration)>, BatchError> {
+    if per_item.is_zero() {
+        return Err(BatchError::InvalidInput(String::from("Per-item duration must be positive")));
+    }
+    let (_, batch_sizes) = even_split(total, max_batch_size)?;
+
+    let mut start = Duration::ZERO;
+    let mut schedule = Vec::with_capacity(batch_sizes.len());
+    for size in batch_sizes {
+        let duration = scale_duration(per_item, size.get())
+            .ok_or(BatchError::Overflow)?;
+        schedule.push((start, duration));
+        start = start
+            .checked_add(duration)
+            .ok_or(BatchError::Overflow)?;
+    }
+
+    Ok(schedule)
+}
+
+/// Splits a total into a linear ramp-up followed by a steady phase.
+///
+/// The first `ramp_batches` batches grow linearly, batch `i` (counting from 1) holding
+/// `ceil(steady_batch_size * i / ramp_batches)` items, so the ramp starts small and its last
+/// batch reaches `steady_batch_size`. The rest of the total is then covered by batches of
+/// `steady_batch_size`, with a final partial batch for whatever is left. If the total runs out
+/// during the ramp, the ramp is cut short by a final partial batch.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `ramp_batches` - The number of batches in the ramp-up phase.
+/// * `steady_batch_size` - The batch size of the steady phase.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The ramp_batches is zero.
+/// * The steady_batch_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_rampup;
+///
+/// let batch_sizes = split_rampup(50, 4, 8).unwrap();
+/// let sizes: Vec<usize> = batch_sizes.iter().map(|b| b.get()).collect();
+/// assert_eq!(sizes, vec![2, 4, 6, 8, 8, 8, 8, 6]);
+/// ```
+pub fn split_rampup(total: usize, ramp_batches: usize, steady_batch_size: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if ramp_batches == 0 {
+        return Err(BatchError::InvalidInput(String::from("Number of ramp batches must be a positive number")));
+    }
+    if steady_batch_size == 0 {
+        return Err(BatchError::InvalidInput(String::from("Steady batch size must be a positive number")));
+    }
+
+    let mut remaining = total;
+    let mut batch_sizes = Vec::new();
+    for i in 1..=ramp_batches {
+        if remaining == 0 {
+            break;
+        }
+        let size = ((steady_batch_size as u128 * i as u128).div_ceil(ramp_batches as u128) as usize).min(remaining);
+        batch_sizes.push(NonZeroUsize::new(size).unwrap());
+        remaining -= size;
+    }
+    while remaining > 0 {
+        let size = steady_batch_size.min(remaining);
+        batch_sizes.push(NonZeroUsize::new(size).unwrap());
+        remaining -= size;
+    }
+
+    Ok(batch_sizes)
+}
+
+/// Pairs each batch of an even split with its scheduled dispatch time.
+///
+/// The batches are those produced by [`even_split`], expressed as contiguous half-open item
+/// ranges covering `0..total`. Batch `i` is scheduled at `start + i * interval`, so a paced
+/// dispatcher can sleep until each instant and then process `&data[range]`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `interval` - The time between consecutive dispatches.
+/// * `start` - The dispatch time of the first batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(dispatch_time, range)` pairs in batch order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+/// * A dispatch time cannot be represented as an `Instant`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::timed_ranges;
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// let timed = timed_ranges(20, 10, Duration::from_secs(1), start).unwrap();
+/// assert_eq!(timed, vec![(start, 0..10), (start + Duration::from_secs(1), 10..20)]);
+/// ```
+#[cfg(feature = "std")]
+pub fn timed_ranges(total: usize, max_batch_size: usize, interval: Duration, start: Instant) -> Result<Vec<(Instant, Range<usize>)>, BatchError> {
+    let (_, batch_sizes) = even_split(total, max_batch_size)?;
+
+    let mut offset = 0;
+    let mut timed = Vec::with_capacity(batch_sizes.len());
+    for (i, size) in batch_sizes.into_iter().enumerate() {
+        let at = scale_duration(interval, i)
+            .and_then(|delay| start.checked_add(delay))
+            .ok_or(BatchError::Overflow)?;
+        timed.push((at, offset..offset + size.get()));
+        offset += size.get();
+    }
+
+    Ok(timed)
+}
+
+/// Describes the pairing schedule of a balanced binary-tree reduction over a number of leaves.
+///
+/// Each level lists the `(left, right)` pairs to combine, where indices refer to the original
+/// leaves and the combined value is stored at `left`, as in an in-place parallel reduction.
+/// The surviving indices of a level are paired up in order; when their count is odd, the last
+/// one is carried unpaired to the next level. The levels continue until a single value remains
+/// at index 0, so zero or one leaves need no levels at all.
+///
+/// # Arguments
+///
+/// * `num_leaves` - The number of leaf batches to reduce.
+///
+/// # Returns
+///
+/// A vector with one entry per reduction level, each holding the pairs combined at that level.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::reduction_schedule;
+///
+/// let schedule = reduction_schedule(5);
+/// assert_eq!(schedule, vec![vec![(0, 1), (2, 3)], vec![(0, 2)], vec![(0, 4)]]);
+/// ```
+pub fn reduction_schedule(num_leaves: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut survivors: Vec<usize> = (0..num_leaves).collect();
+    let mut levels = Vec::new();
+    while survivors.len() > 1 {
+        let pairs: Vec<(usize, usize)> = survivors.chunks_exact(2).map(|pair| (pair[0], pair[1])).collect();
+        survivors = survivors.iter().copied().step_by(2).collect();
+        levels.push(pairs);
+    }
+    levels
+}
+
+/// Groups dependent batches into execution waves that respect a concurrency limit.
+///
+/// Each dependency `(a, b)` means batch `b` may only run once batch `a` has finished. Waves are
+/// built greedily: every wave takes the lowest-indexed batches whose dependencies all ran in
+/// earlier waves, up to `max_concurrent` of them. Ready batches that do not fit are deferred to
+/// the next wave.
+///
+/// # Arguments
+///
+/// * `num_batches` - The number of batches, indexed `0..num_batches`.
+/// * `deps` - The `(prerequisite, dependent)` pairs.
+/// * `max_concurrent` - The maximum number of batches per wave.
+///
+/// # Returns
+///
+/// A `Result` containing the waves in execution order, each listing its batch indices in
+/// ascending order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The num_batches is zero.
+/// * The max_concurrent is zero.
+/// * A dependency refers to a batch outside `0..num_batches`.
+/// * The dependencies contain a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::schedule_with_concurrency;
+///
+/// let waves = schedule_with_concurrency(4, &[(0, 1), (0, 2), (1, 3), (2, 3)], 2).unwrap();
+/// assert_eq!(waves, vec![vec![0], vec![1, 2], vec![3]]);
+/// ```
+pub fn schedule_with_concurrency(num_batches: usize, deps: &[(usize, usize)], max_concurrent: usize) -> Result<Vec<Vec<usize>>, BatchError> {
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if max_concurrent == 0 {
+        return Err(BatchError::InvalidInput(String::from("Maximum concurrency must be a positive number")));
+    }
+
+    let mut dependents = vec![Vec::new(); num_batches];
+    let mut pending = vec![0usize; num_batches];
+    for &(before, after) in deps {
+        if before >= num_batches || after >= num_batches {
+            return Err(BatchError::InvalidInput(format!("Dependency ({}, {}) refers to a batch out of range", before, after)));
+        }
+        dependents[before].push(after);
+        pending[after] += 1;
+    }
+
+    let mut ready: Vec<usize> = (0..num_batches).filter(|&batch| pending[batch] == 0).collect();
+    let mut waves = Vec::new();
+    let mut scheduled = 0;
+    while !ready.is_empty() {
+        ready.sort_unstable();
+        let rest = ready.split_off(ready.len().min(max_concurrent));
+        let wave = core::mem::replace(&mut ready, rest);
+        for &batch in &wave {
+            for &dependent in &dependents[batch] {
+                pending[dependent] -= 1;
+                if pending[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+        scheduled += wave.len();
+        waves.push(wave);
+    }
+    if scheduled < num_batches {
+        return Err(BatchError::ImpossibleConstraint(String::from("Dependencies contain a cycle")));
+    }
+
+    Ok(waves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gantt_schedule_contiguous() {
+        let per_item = Duration::from_micros(250);
+        let schedule = gantt_schedule(50, 8, per_item).unwrap();
+        assert_eq!(schedule.len(), 10);
+        assert_eq!(schedule[0].0, Duration::ZERO);
+        for pair in schedule.windows(2) {
+            assert_eq!(pair[1].0, pair[0].0 + pair[0].1);
+        }
+        let (last_start, last_duration) = *schedule.last().unwrap();
+        assert_eq!(last_start + last_duration, per_item * 50);
+    }
+
+    #[test]
+    fn test_gantt_schedule_errors() {
+        assert!(gantt_schedule(0, 8, Duration::from_millis(1)).is_err());
+        assert!(gantt_schedule(10, 0, Duration::from_millis(1)).is_err());
+        assert!(gantt_schedule(10, 8, Duration::ZERO).is_err());
+        assert!(gantt_schedule(10, 8, Duration::MAX).is_err());
+    }
+
+    #[test]
+    fn test_scale_duration_large_counts() {
+        assert_eq!(scale_duration(Duration::from_nanos(1), usize::MAX), Some(Duration::from_nanos(usize::MAX as u64)));
+        assert_eq!(scale_duration(Duration::from_millis(1500), 1 << 20), Some(Duration::from_secs(3 << 19)));
+        assert_eq!(scale_duration(Duration::from_secs(u64::MAX / 2), 3), None);
+        assert_eq!(scale_duration(Duration::MAX, 0), Some(Duration::ZERO));
+        let schedule = gantt_schedule(usize::MAX, usize::MAX, Duration::from_nanos(1)).unwrap();
+        assert_eq!(schedule, vec![(Duration::ZERO, Duration::from_nanos(usize::MAX as u64))]);
+    }
+
+    #[test]
+    fn test_split_rampup() {
+        let batch_sizes = split_rampup(1000, 5, 100).unwrap();
+        let sizes: Vec<usize> = batch_sizes.iter().map(|b| b.get()).collect();
+        assert_eq!(sizes.iter().sum::<usize>(), 1000);
+        assert_eq!(&sizes[..5], &[20, 40, 60, 80, 100]);
+        assert!(sizes[..5].windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sizes[5..sizes.len() - 1].iter().all(|&s| s == 100));
+        assert!(*sizes.last().unwrap() <= 100);
+    }
+
+    #[test]
+    fn test_split_rampup_short_total() {
+        let sizes: Vec<usize> = split_rampup(5, 4, 8).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(sizes, vec![2, 3]);
+        let sizes: Vec<usize> = split_rampup(3, 1, 8).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(sizes, vec![3]);
+    }
+
+    #[test]
+    fn test_split_rampup_errors() {
+        assert!(split_rampup(0, 4, 8).is_err());
+        assert!(split_rampup(10, 0, 8).is_err());
+        assert!(split_rampup(10, 4, 0).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timed_ranges() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(100);
+        let timed = timed_ranges(50, 8, interval, start).unwrap();
+        assert_eq!(timed.len(), 10);
+        assert_eq!(timed[0].0, start);
+        assert_eq!(timed[0].1.start, 0);
+        assert_eq!(timed.last().unwrap().1.end, 50);
+        for pair in timed.windows(2) {
+            assert_eq!(pair[1].0 - pair[0].0, interval);
+            assert_eq!(pair[0].1.end, pair[1].1.start);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_timed_ranges_errors() {
+        let start = Instant::now();
+        assert!(timed_ranges(0, 8, Duration::from_millis(1), start).is_err());
+        assert!(timed_ranges(10, 0, Duration::from_millis(1), start).is_err());
+        assert!(timed_ranges(10, 1, Duration::MAX, start).is_err());
+    }
+
+    #[test]
+    fn test_reduction_schedule_clean_tree() {
+        assert_eq!(reduction_schedule(4), vec![vec![(0, 1), (2, 3)], vec![(0, 2)]]);
+    }
+
+    #[test]
+    fn test_reduction_schedule_with_carry() {
+        assert_eq!(reduction_schedule(5), vec![vec![(0, 1), (2, 3)], vec![(0, 2)], vec![(0, 4)]]);
+        assert_eq!(reduction_schedule(3), vec![vec![(0, 1)], vec![(0, 2)]]);
+        assert!(reduction_schedule(1).is_empty());
+        assert!(reduction_schedule(0).is_empty());
+    }
+
+    #[test]
+    fn test_reduction_schedule_combines_every_leaf_once() {
+        for num_leaves in 1..40 {
+            let schedule = reduction_schedule(num_leaves);
+            let mut consumed: Vec<usize> = schedule.iter().flatten().map(|&(_, right)| right).collect();
+            consumed.sort_unstable();
+            assert_eq!(consumed, (1..num_leaves).collect::<Vec<_>>());
+            assert_eq!(schedule.len(), (num_leaves as f64).log2().ceil() as usize);
+        }
+    }
+
+    #[test]
+    fn test_schedule_with_concurrency() {
+        // A chain 0 -> 1 -> 2 alongside independent branches 3, 4, 5 and 6, where 6 needs 0.
+        let deps = [(0, 1), (1, 2), (0, 6)];
+        let waves = schedule_with_concurrency(7, &deps, 3).unwrap();
+        assert_eq!(waves, vec![vec![0, 3, 4], vec![1, 5, 6], vec![2]]);
+
+        let mut wave_of = [0; 7];
+        for (w, wave) in waves.iter().enumerate() {
+            assert!(wave.len() <= 3);
+            for &batch in wave {
+                wave_of[batch] = w;
+            }
+        }
+        assert!(deps.iter().all(|&(before, after)| wave_of[before] < wave_of[after]));
+        assert_eq!(schedule_with_concurrency(7, &deps, 1).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_schedule_with_concurrency_errors() {
+        assert!(schedule_with_concurrency(0, &[], 2).is_err());
+        assert!(schedule_with_concurrency(3, &[], 0).is_err());
+        assert!(schedule_with_concurrency(3, &[(0, 3)], 2).is_err());
+        assert!(schedule_with_concurrency(3, &[(0, 1), (1, 2), (2, 1)], 2).is_err());
+        assert!(schedule_with_concurrency(2, &[(1, 1)], 2).is_err());
+    }
+}
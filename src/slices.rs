@@ -0,0 +1,129 @@
+//! Helpers that apply splits to actual slices instead of returning sizes.
+
+use alloc::vec::Vec;
+
+use crate::{split_by_count, BatchError};
+
+/// Splits a slice into `num_batches` borrowed sub-slices without copying.
+///
+/// The chunk lengths are those computed by [`split_by_count`] for `items.len()`, so the first
+/// `items.len() % num_batches` chunks are one element longer. The chunks are contiguous and
+/// tile the input exactly.
+///
+/// # Arguments
+///
+/// * `items` - The slice to split.
+/// * `num_batches` - The number of chunks to produce.
+///
+/// # Returns
+///
+/// A `Result` containing the chunks in order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The slice is empty.
+/// * The number of batches is zero.
+/// * The slice is shorter than the number of batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_slice;
+///
+/// let items = [1, 2, 3, 4, 5, 6, 7];
+/// let chunks = split_slice(&items, 3).unwrap();
+/// assert_eq!(chunks, vec![&[1, 2, 3][..], &[4, 5], &[6, 7]]);
+/// ```
+pub fn split_slice<T>(items: &[T], num_batches: usize) -> Result<Vec<&[T]>, BatchError> {
+    let batch_sizes = split_by_count(items.len(), num_batches)?;
+
+    let mut rest = items;
+    let mut chunks = Vec::with_capacity(batch_sizes.len());
+    for size in batch_sizes {
+        let (chunk, tail) = rest.split_at(size.get());
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    Ok(chunks)
+}
+
+/// Splits a mutable slice into `num_batches` disjoint mutable sub-slices without copying.
+///
+/// This is the mutable counterpart of [`split_slice`], with the same chunk lengths, so each chunk
+/// can be handed to a different worker for in-place processing.
+///
+/// # Arguments
+///
+/// * `items` - The slice to split.
+/// * `num_batches` - The number of chunks to produce.
+///
+/// # Returns
+///
+/// A `Result` containing the chunks in order.
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_slice`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_slice_mut;
+///
+/// let mut items = [0; 5];
+/// for (i, chunk) in split_slice_mut(&mut items, 2).unwrap().into_iter().enumerate() {
+///     chunk.fill(i);
+/// }
+/// assert_eq!(items, [0, 0, 0, 1, 1]);
+/// ```
+pub fn split_slice_mut<T>(items: &mut [T], num_batches: usize) -> Result<Vec<&mut [T]>, BatchError> {
+    let batch_sizes = split_by_count(items.len(), num_batches)?;
+
+    let mut rest = items;
+    let mut chunks = Vec::with_capacity(batch_sizes.len());
+    for size in batch_sizes {
+        let (chunk, tail) = rest.split_at_mut(size.get());
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_slice_tiles_input() {
+        let items: Vec<usize> = (0..103).collect();
+        let chunks = split_slice(&items, 10).unwrap();
+        assert_eq!(chunks.len(), 10);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), items.len());
+        assert_eq!(chunks.concat(), items);
+        let lengths: Vec<usize> = chunks.iter().map(|c| c.len()).collect();
+        let expected: Vec<usize> = split_by_count(103, 10).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(lengths, expected);
+    }
+
+    #[test]
+    fn test_split_slice_mut_disjoint() {
+        let mut items = vec![0usize; 10];
+        let chunks = split_slice_mut(&mut items, 4).unwrap();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            chunk.iter_mut().for_each(|item| *item = i);
+        }
+        assert_eq!(items, vec![0, 0, 0, 1, 1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_split_slice_errors() {
+        let empty: [u8; 0] = [];
+        assert_eq!(split_slice(&empty, 2), Err(BatchError::ZeroTotal));
+        assert_eq!(split_slice(&[1, 2], 0), Err(BatchError::ZeroBatchCount));
+        assert!(split_slice(&[1, 2], 3).is_err());
+        assert!(split_slice_mut(&mut [1, 2], 3).is_err());
+    }
+}
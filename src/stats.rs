@@ -0,0 +1,239 @@
+//! Summaries that describe the quality of a computed split.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::BatchPlan;
+
+/// Counts how many batches have each distinct size.
+///
+/// Balanced splits such as those of [`split_by_count`](crate::split_by_count) have at most two
+/// distinct sizes, one apart, which the histogram makes easy to check.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to summarize.
+///
+/// # Returns
+///
+/// A map from each distinct batch size to the number of batches with that size, in increasing
+/// order of size.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{size_histogram, split_by_count};
+/// use std::collections::BTreeMap;
+///
+/// let histogram = size_histogram(&split_by_count(10, 3).unwrap());
+/// assert_eq!(histogram, BTreeMap::from([(3, 2), (4, 1)]));
+/// ```
+pub fn size_histogram(batches: &[NonZeroUsize]) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+    for size in batches {
+        *histogram.entry(size.get()).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Returns the difference between the largest and smallest batch sizes.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to compare.
+///
+/// # Returns
+///
+/// The spread of the sizes, or zero when there are no batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{max_minus_min, split_by_count};
+///
+/// assert_eq!(max_minus_min(&split_by_count(10, 3).unwrap()), 1);
+/// assert_eq!(max_minus_min(&split_by_count(9, 3).unwrap()), 0);
+/// ```
+pub fn max_minus_min(batches: &[NonZeroUsize]) -> usize {
+    match (batches.iter().max(), batches.iter().min()) {
+        (Some(max), Some(min)) => max.get() - min.get(),
+        _ => 0,
+    }
+}
+
+/// Measures how uneven a split is, relative to its mean batch size.
+///
+/// The imbalance is `(max - min) / mean`, so it is zero for a perfectly even split and does not
+/// depend on the scale of the total. A balanced split of `total` into `n` batches has a spread
+/// of at most one, so its imbalance is at most `n / total`.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to measure.
+///
+/// # Returns
+///
+/// The imbalance, or zero when there are no batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{imbalance, split_by_count};
+///
+/// assert_eq!(imbalance(&split_by_count(9, 3).unwrap()), 0.0);
+/// assert_eq!(imbalance(&split_by_count(10, 4).unwrap()), 0.4);
+/// ```
+pub fn imbalance(batches: &[NonZeroUsize]) -> f64 {
+    if batches.is_empty() {
+        return 0.0;
+    }
+    let sum: u128 = batches.iter().map(|size| size.get() as u128).sum();
+    let mean = sum as f64 / batches.len() as f64;
+    max_minus_min(batches) as f64 / mean
+}
+
+/// Summarizes a plan in one human-readable line.
+///
+/// A uniform plan is described by its single batch size, followed by the remainder. Otherwise
+/// the distinct sizes are listed as `count×size` in increasing order of size, as counted by
+/// [`size_histogram`], with the remainder appended when it is not zero.
+///
+/// # Arguments
+///
+/// * `plan` - The plan to describe.
+///
+/// # Returns
+///
+/// The summary line.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{describe_split, even_split, split_by_count, BatchPlan};
+///
+/// let plan = BatchPlan::from(even_split(128, 8).unwrap());
+/// assert_eq!(describe_split(&plan), "128 split into 16 batches of 8 (0 remainder)");
+/// let plan = BatchPlan { total: 10, sizes: split_by_count(10, 3).unwrap(), remainder: 0 };
+/// assert_eq!(describe_split(&plan), "10 split into 3 batches: 2×3, 1×4");
+/// ```
+pub fn describe_split(plan: &BatchPlan) -> String {
+    let histogram = size_histogram(&plan.sizes);
+    let batches = if plan.sizes.len() == 1 { "batch" } else { "batches" };
+    match histogram.len() {
+        0 => format!("{} split into 0 batches ({} remainder)", plan.total, plan.remainder),
+        1 => format!("{} split into {} {} of {} ({} remainder)", plan.total, plan.sizes.len(), batches, plan.sizes[0], plan.remainder),
+        _ => {
+            let groups: Vec<String> = histogram.iter().map(|(size, count)| format!("{}×{}", count, size)).collect();
+            let mut description = format!("{} split into {} {}: {}", plan.total, plan.sizes.len(), batches, groups.join(", "));
+            if plan.remainder > 0 {
+                description.push_str(&format!(" ({} remainder)", plan.remainder));
+            }
+            description
+        }
+    }
+}
+
+/// Draws a split as a text bar chart, one line per batch.
+///
+/// Each line is a bar of `#` characters followed by a space and the batch size. Bars are scaled
+/// so that the largest batch fills `width` characters; smaller batches round up, so every batch
+/// gets at least one character while `width` is positive. With a `width` of zero the bars are
+/// omitted and each line holds just the size. Lines are separated by `\n`, with no trailing
+/// newline, and an empty split renders as an empty string.
+///
+/// # Arguments
+///
+/// * `batches` - The batch sizes to draw.
+/// * `width` - The length of the bar for the largest batch.
+///
+/// # Returns
+///
+/// The rendered chart.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{render_bars, split_weighted};
+///
+/// let batch_sizes = split_weighted(7, vec![4, 3]).unwrap();
+/// assert_eq!(render_bars(&batch_sizes, 8), "######## 4\n###### 3");
+/// ```
+pub fn render_bars(batches: &[NonZeroUsize], width: usize) -> String {
+    let Some(largest) = batches.iter().max() else {
+        return String::new();
+    };
+
+    let lines: Vec<String> = batches
+        .iter()
+        .map(|size| {
+            let bar_len = (size.get() as u128 * width as u128).div_ceil(largest.get() as u128) as usize;
+            if bar_len == 0 {
+                format!("{}", size)
+            } else {
+                format!("{} {}", "#".repeat(bar_len), size)
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{split_by_count, split_weighted};
+
+    #[test]
+    fn test_size_histogram() {
+        assert!(size_histogram(&[]).is_empty());
+        for total in 1..60 {
+            for num_batches in 1..=total {
+                let histogram = size_histogram(&split_by_count(total, num_batches).unwrap());
+                assert!(histogram.len() <= 2);
+                assert_eq!(histogram.values().sum::<usize>(), num_batches);
+                assert_eq!(histogram.iter().map(|(size, count)| size * count).sum::<usize>(), total);
+            }
+        }
+    }
+
+    #[test]
+    fn test_imbalance_bounds() {
+        assert_eq!((imbalance(&[]), max_minus_min(&[])), (0.0, 0));
+        for total in 1..80 {
+            for num_batches in 1..=total {
+                let batch_sizes = split_by_count(total, num_batches).unwrap();
+                assert!(max_minus_min(&batch_sizes) <= 1);
+                assert!(imbalance(&batch_sizes) <= num_batches as f64 / total as f64 + 1e-12);
+            }
+        }
+        let skewed = split_weighted(100, vec![1, 3]).unwrap();
+        assert_eq!(max_minus_min(&skewed), 50);
+        assert_eq!(imbalance(&skewed), 1.0);
+        assert_eq!(imbalance(&[NonZeroUsize::MAX, NonZeroUsize::MAX]), 0.0);
+    }
+
+    #[test]
+    fn test_describe_split() {
+        let plan = crate::split_with_remainder_plan(50, 8).unwrap();
+        assert_eq!(describe_split(&plan), "50 split into 6 batches of 8 (2 remainder)");
+        let plan = BatchPlan { total: 12, sizes: split_by_count(11, 3).unwrap(), remainder: 1 };
+        assert_eq!(describe_split(&plan), "12 split into 3 batches: 1×3, 2×4 (1 remainder)");
+        let plan = BatchPlan { total: 7, sizes: vec![NonZeroUsize::new(7).unwrap()], remainder: 0 };
+        assert_eq!(describe_split(&plan), "7 split into 1 batch of 7 (0 remainder)");
+        let plan = BatchPlan { total: 3, sizes: Vec::new(), remainder: 3 };
+        assert_eq!(describe_split(&plan), "3 split into 0 batches (3 remainder)");
+    }
+
+    #[test]
+    fn test_render_bars() {
+        let to_batches = |sizes: &[usize]| sizes.iter().map(|&s| NonZeroUsize::new(s).unwrap()).collect::<Vec<_>>();
+        assert_eq!(render_bars(&to_batches(&[4, 3]), 4), "#### 4\n### 3");
+        assert_eq!(render_bars(&to_batches(&[100, 1, 50]), 10), "########## 100\n# 1\n##### 50");
+        assert_eq!(render_bars(&to_batches(&[5]), 3), "### 5");
+        assert_eq!(render_bars(&to_batches(&[4, 3]), 0), "4\n3");
+        assert_eq!(render_bars(&[], 10), "");
+        assert_eq!(render_bars(&[NonZeroUsize::MAX], 2), format!("## {}", usize::MAX));
+    }
+}
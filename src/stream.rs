@@ -0,0 +1,349 @@
+//! Strategies driven by feedback that is only available while batches are being produced.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use core::time::Duration;
+
+use crate::BatchError;
+
+/// Splits a total into batches sized by a consumer's current capacity.
+///
+/// Before each batch the `capacity` closure is asked how many items the consumer can accept
+/// right now, and the batch size is `min(max_batch_size, remaining, capacity)`. Returning
+/// `None` (or a capacity of zero) stops the split early, returning the batches produced so far.
+/// This models consumer-driven backpressure where downstream capacity varies over time.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `max_batch_size` - The maximum allowed size for each batch.
+/// * `capacity` - A closure returning the consumer's current capacity, or `None` to stop.
+///
+/// # Returns
+///
+/// A `Result` containing the batch sizes produced before the total was covered or the consumer
+/// stopped accepting. They sum to `total` unless the split was stopped early.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The max_batch_size is zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_pull;
+/// use std::num::NonZeroUsize;
+///
+/// let mut capacities = vec![3, 10, 1].into_iter();
+/// let batch_sizes = split_pull(10, 5, || capacities.next()).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(5).unwrap(), NonZeroUsize::new(1).unwrap()]);
+/// ```
+pub fn split_pull<F: FnMut() -> Option<usize>>(total: usize, max_batch_size: usize, mut capacity: F) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if max_batch_size == 0 {
+        return Err(BatchError::ZeroMaxBatchSize);
+    }
+
+    let mut remaining = total;
+    let mut batch_sizes = Vec::new();
+    while remaining > 0 {
+        let Some(size) = capacity().and_then(|c| NonZeroUsize::new(c.min(max_batch_size).min(remaining))) else {
+            break;
+        };
+        batch_sizes.push(size);
+        remaining -= size.get();
+    }
+
+    Ok(batch_sizes)
+}
+
+/// The weight given to the newest throughput sample in [`AdaptiveSplitter`]'s moving average.
+const THROUGHPUT_SMOOTHING: f64 = 0.5;
+
+/// The largest factor by which [`AdaptiveSplitter`] changes the batch size in a single step.
+const MAX_ADJUSTMENT: f64 = 2.0;
+
+/// A splitter that adapts the batch size to observed batch timings.
+///
+/// The caller alternates between [`next_size`](AdaptiveSplitter::next_size), which hands out the
+/// next batch, and [`record`](AdaptiveSplitter::record), which reports how long that batch took.
+/// The splitter keeps an exponential moving average of the observed throughput (items per
+/// second) and moves the batch size toward the size that would take exactly
+/// `target_batch_duration` at that throughput. Each adjustment is bounded to a factor of two in
+/// either direction, and the batch size never drops below one.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::AdaptiveSplitter;
+/// use std::time::Duration;
+///
+/// let mut splitter = AdaptiveSplitter::new(100, Duration::from_millis(10)).unwrap();
+/// assert_eq!(splitter.next_size(1000).unwrap().get(), 100);
+/// splitter.record(Duration::from_millis(20));
+/// assert_eq!(splitter.next_size(900).unwrap().get(), 50);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveSplitter {
+    batch_size: f64,
+    target_batch_duration: Duration,
+    throughput: Option<f64>,
+    last_size: usize,
+}
+
+impl AdaptiveSplitter {
+    /// Creates a splitter starting at `initial_batch_size` and aiming for batches that take
+    /// `target_batch_duration` to process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial batch size or the target duration is zero.
+    pub fn new(initial_batch_size: usize, target_batch_duration: Duration) -> Result<Self, BatchError> {
+        if initial_batch_size == 0 {
+            return Err(BatchError::InvalidInput(String::from("Initial batch size must be a positive number")));
+        }
+        if target_batch_duration.is_zero() {
+            return Err(BatchError::InvalidInput(String::from("Target batch duration must be positive")));
+        }
+
+        Ok(AdaptiveSplitter {
+            batch_size: initial_batch_size as f64,
+            target_batch_duration,
+            throughput: None,
+            last_size: 0,
+        })
+    }
+
+    /// Returns the batch size the splitter currently aims for.
+    pub fn batch_size(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.batch_size as usize).unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Returns the size of the next batch, capped at `remaining`, or `None` once nothing remains.
+    pub fn next_size(&mut self, remaining: usize) -> Option<NonZeroUsize> {
+        let size = NonZeroUsize::new(self.batch_size().get().min(remaining))?;
+        self.last_size = size.get();
+        Some(size)
+    }
+
+    /// Reports how long the batch last returned by `next_size` took to process.
+    ///
+    /// Reports made without a preceding `next_size` are ignored.
+    pub fn record(&mut self, actual: Duration) {
+        if self.last_size == 0 {
+            return;
+        }
+        let size = core::mem::take(&mut self.last_size) as f64;
+
+        let current = self.batch_size;
+        let desired = if actual.is_zero() {
+            f64::INFINITY
+        } else {
+            let sample = size / actual.as_secs_f64();
+            let throughput = match self.throughput {
+                Some(average) => THROUGHPUT_SMOOTHING * sample + (1.0 - THROUGHPUT_SMOOTHING) * average,
+                None => sample,
+            };
+            self.throughput = Some(throughput);
+            throughput * self.target_batch_duration.as_secs_f64()
+        };
+
+        self.batch_size = desired.clamp(current / MAX_ADJUSTMENT, current * MAX_ADJUSTMENT).max(1.0);
+    }
+}
+
+/// Groups items that arrive over time into batches of at most `max_batch_size`.
+///
+/// This is the online counterpart of splitting a known total: items are reported with
+/// [`push`](BatchAccumulator::push) as they arrive, a full batch of `max_batch_size` is handed
+/// out as soon as enough items are buffered, and [`flush`](BatchAccumulator::flush) hands out
+/// whatever is left once the input ends. A single push may complete several batches; the extra
+/// ones are returned by [`pop`](BatchAccumulator::pop), and by `flush` before the final partial
+/// batch.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::BatchAccumulator;
+///
+/// let mut accumulator = BatchAccumulator::new(4).unwrap();
+/// assert_eq!(accumulator.push(3), None);
+/// assert_eq!(accumulator.push(3).map(|b| b.get()), Some(4));
+/// assert_eq!(accumulator.flush().map(|b| b.get()), Some(2));
+/// assert_eq!(accumulator.flush(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchAccumulator {
+    max_batch_size: NonZeroUsize,
+    buffered: usize,
+    ready: usize,
+}
+
+impl BatchAccumulator {
+    /// Creates an empty accumulator producing batches of at most `max_batch_size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the max_batch_size is zero.
+    pub fn new(max_batch_size: usize) -> Result<Self, BatchError> {
+        let max_batch_size = NonZeroUsize::new(max_batch_size).ok_or(BatchError::ZeroMaxBatchSize)?;
+        Ok(BatchAccumulator { max_batch_size, buffered: 0, ready: 0 })
+    }
+
+    /// Returns the number of items buffered and not yet handed out, counting full batches.
+    pub fn pending(&self) -> usize {
+        self.ready.saturating_mul(self.max_batch_size.get()).saturating_add(self.buffered)
+    }
+
+    /// Adds `count` newly arrived items, returning a full batch if one is ready.
+    pub fn push(&mut self, count: usize) -> Option<NonZeroUsize> {
+        let max_batch_size = self.max_batch_size.get();
+        self.ready = self.ready.saturating_add(count / max_batch_size);
+        let rest = count % max_batch_size;
+        if rest >= max_batch_size - self.buffered {
+            self.ready = self.ready.saturating_add(1);
+            self.buffered -= max_batch_size - rest;
+        } else {
+            self.buffered += rest;
+        }
+        self.pop()
+    }
+
+    /// Returns a full batch if one is ready, without adding items.
+    pub fn pop(&mut self) -> Option<NonZeroUsize> {
+        if self.ready == 0 {
+            return None;
+        }
+        self.ready -= 1;
+        Some(self.max_batch_size)
+    }
+
+    /// Returns the next batch once the input has ended: a full batch while any are ready, then
+    /// the partial batch of the remaining items, then `None`.
+    pub fn flush(&mut self) -> Option<NonZeroUsize> {
+        self.pop().or_else(|| NonZeroUsize::new(core::mem::take(&mut self.buffered)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_accumulator_matches_split_with_remainder() {
+        for max in 1..9 {
+            let mut accumulator = BatchAccumulator::new(max).unwrap();
+            let mut batches = Vec::new();
+            for count in [0, 5, 1, 13, 2, 0, 7] {
+                batches.extend(accumulator.push(count));
+                while let Some(batch) = accumulator.pop() {
+                    batches.push(batch);
+                }
+            }
+            assert_eq!(accumulator.pending(), 28 % max);
+            while let Some(batch) = accumulator.flush() {
+                batches.push(batch);
+            }
+            let (_, mut expected, remainder) = crate::split_with_remainder(28, max).unwrap();
+            expected.extend(NonZeroUsize::new(remainder));
+            assert_eq!(batches, expected);
+        }
+    }
+
+    #[test]
+    fn test_batch_accumulator_flush_drains_full_batches() {
+        let mut accumulator = BatchAccumulator::new(3).unwrap();
+        assert_eq!(accumulator.push(10).map(|b| b.get()), Some(3));
+        assert_eq!(accumulator.pending(), 7);
+        let flushed: Vec<usize> = core::iter::from_fn(|| accumulator.flush()).map(|b| b.get()).collect();
+        assert_eq!(flushed, vec![3, 3, 1]);
+        assert_eq!(accumulator.pending(), 0);
+        let mut large = BatchAccumulator::new(usize::MAX).unwrap();
+        assert_eq!(large.push(usize::MAX - 1), None);
+        assert_eq!(large.push(3), Some(NonZeroUsize::MAX));
+        assert_eq!(large.flush().map(|b| b.get()), Some(2));
+        assert_eq!(BatchAccumulator::new(0), Err(BatchError::ZeroMaxBatchSize));
+    }
+
+    #[test]
+    fn test_split_pull_shrinking_capacity() {
+        let mut capacity = 8;
+        let batch_sizes = split_pull(20, 6, || {
+            let current = capacity;
+            capacity = (capacity / 2).max(1);
+            Some(current)
+        })
+        .unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![6, 4, 2, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 20);
+    }
+
+    #[test]
+    fn test_split_pull_stops_early() {
+        let mut calls = 0;
+        let batch_sizes = split_pull(100, 10, || {
+            calls += 1;
+            if calls <= 2 { Some(10) } else { None }
+        })
+        .unwrap();
+        assert_eq!(batch_sizes, vec![NonZeroUsize::new(10).unwrap(); 2]);
+        assert_eq!(split_pull(100, 10, || Some(0)), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_split_pull_errors() {
+        assert!(split_pull(0, 10, || Some(1)).is_err());
+        assert!(split_pull(10, 0, || Some(1)).is_err());
+    }
+
+    #[test]
+    fn test_adaptive_splitter_shrinks_when_slow() {
+        let mut splitter = AdaptiveSplitter::new(64, Duration::from_millis(100)).unwrap();
+        let mut sizes = Vec::new();
+        for _ in 0..5 {
+            let size = splitter.next_size(usize::MAX).unwrap().get();
+            sizes.push(size);
+            // Every item takes 10ms, so the target is reached at 10 items per batch.
+            splitter.record(Duration::from_millis(10) * size as u32 * 2);
+        }
+        assert!(sizes.windows(2).all(|pair| pair[1] < pair[0]));
+        let size = splitter.next_size(usize::MAX).unwrap().get();
+        assert!(size < 64);
+    }
+
+    #[test]
+    fn test_adaptive_splitter_grows_when_fast() {
+        let mut splitter = AdaptiveSplitter::new(4, Duration::from_millis(100)).unwrap();
+        let mut sizes = Vec::new();
+        for _ in 0..4 {
+            let size = splitter.next_size(usize::MAX).unwrap().get();
+            sizes.push(size);
+            splitter.record(Duration::from_millis(1) * size as u32);
+        }
+        assert_eq!(sizes, vec![4, 8, 16, 32]);
+        assert_eq!(splitter.batch_size().get(), 64);
+    }
+
+    #[test]
+    fn test_adaptive_splitter_converges_and_caps() {
+        let mut splitter = AdaptiveSplitter::new(10, Duration::from_millis(50)).unwrap();
+        for _ in 0..20 {
+            let size = splitter.next_size(usize::MAX).unwrap().get();
+            splitter.record(Duration::from_millis(2) * size as u32);
+        }
+        assert_eq!(splitter.batch_size().get(), 25);
+        assert_eq!(splitter.next_size(3).unwrap().get(), 3);
+        assert_eq!(splitter.next_size(0), None);
+    }
+
+    #[test]
+    fn test_adaptive_splitter_errors() {
+        assert!(AdaptiveSplitter::new(0, Duration::from_millis(1)).is_err());
+        assert!(AdaptiveSplitter::new(1, Duration::ZERO).is_err());
+    }
+}
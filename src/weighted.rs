@@ -0,0 +1,1147 @@
+//! Weighted and proportional splitting strategies.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use crate::generic::weighted_sizes;
+use crate::{split_by_count, split_weighted, BatchError};
+
+/// Apportions `total` across `weights` with the largest-remainder method.
+///
+/// Each entry first receives the floor of its exact proportional share, then the units lost
+/// to rounding go one at a time to the entries with the largest fractional parts (ties break
+/// toward lower indices). The result always sums to `total`.
+///
+/// The weights are first scaled to integers relative to the largest one, keeping the 53 bits
+/// an `f64` mantissa holds, and then apportioned exactly in `u128` arithmetic; scaling `total`
+/// as an `f64` would lose precision beyond 2^53 and could round shares up past their exact
+/// value. Weights below 2^-53 of the largest one count as zero.
+///
+/// Callers must ensure `weights` is non-empty, every weight is finite and non-negative, and at
+/// least one weight is positive.
+pub(crate) fn apportion_f64(total: usize, weights: &[f64]) -> Vec<usize> {
+    const SCALE: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+    let max_weight = weights.iter().copied().fold(0.0, f64::max);
+    let scaled: Vec<u128> = weights.iter().map(|&weight| (weight / max_weight * SCALE + 0.5) as u128).collect();
+    largest_remainder_u128(total, &scaled)
+}
+
+/// Splits a total across workers in proportion to their processing speeds.
+///
+/// Faster workers receive more items so that every worker finishes at approximately the same
+/// time, minimizing the overall completion time (makespan). The counts are apportioned with the
+/// largest-remainder method, so their ratios track the speed ratios as closely as whole items
+/// allow while still summing exactly to `total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number of items to distribute.
+/// * `speeds` - The relative processing speed of each worker.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` with the number of items for each worker.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The speeds slice is empty.
+/// * Any speed is zero, negative, infinite or NaN.
+/// * The total is too small to give every worker at least one item.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_by_speed;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_by_speed(60, &[1.0, 2.0, 3.0]).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(10).unwrap(), NonZeroUsize::new(20).unwrap(), NonZeroUsize::new(30).unwrap()]);
+/// ```
+pub fn split_by_speed(total: usize, speeds: &[f64]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if speeds.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = speeds.iter().position(|&s| !s.is_finite() || s <= 0.0) {
+        return Err(BatchError::InvalidWeight { index });
+    }
+
+    apportion_f64(total, speeds)
+        .into_iter()
+        .map(|size| NonZeroUsize::new(size).ok_or_else(|| BatchError::ImpossibleConstraint(String::from("Total is too small to give every worker at least one item"))))
+        .collect()
+}
+
+/// Splits the total based on weights, pairing each batch size with the weight that produced it.
+///
+/// This is [`split_weighted`] with the correspondence between input weights and output sizes
+/// made explicit, which makes apportionment surprises easy to spot in logs. The realized
+/// fraction of each batch can be derived as `size / total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `(weight, size)` pairs in the same order as `weights`.
+///
+/// # Errors
+///
+/// Returns the same errors as [`split_weighted`].
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_annotated;
+/// use std::num::NonZeroUsize;
+///
+/// let annotated = split_weighted_annotated(100, &[1, 3]).unwrap();
+/// assert_eq!(annotated, vec![(1, NonZeroUsize::new(25).unwrap()), (3, NonZeroUsize::new(75).unwrap())]);
+/// ```
+pub fn split_weighted_annotated(total: usize, weights: &[usize]) -> Result<Vec<(usize, NonZeroUsize)>, BatchError> {
+    let batches = split_weighted(total, weights.to_vec())?;
+    Ok(weights.iter().copied().zip(batches).collect())
+}
+
+/// Splits the total based on floating-point weights.
+///
+/// The weights are normalized by their sum, and every batch but the last receives its
+/// proportional share of `total` rounded to the nearest integer, capped at what is left. The
+/// last batch absorbs the rounding drift, exactly like [`split_weighted`], so the sizes always
+/// sum to `total`. This avoids the precision lost by scaling measured ratios such as
+/// `[0.1, 0.25, 0.65]` to integers first.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty.
+/// * Any weight is zero, negative, infinite or NaN.
+/// * The total is too small to give every batch at least one unit.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_f64;
+///
+/// let batch_sizes = split_weighted_f64(200, &[0.1, 0.25, 0.65]).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![20, 50, 130]);
+/// ```
+pub fn split_weighted_f64(total: usize, weights: &[f64]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = weights.iter().position(|&w| !w.is_finite() || w < 0.0) {
+        return Err(BatchError::InvalidWeight { index });
+    }
+    if let Some(index) = weights.iter().position(|&w| w == 0.0) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+
+    let weight_sum: f64 = weights.iter().sum();
+    if !weight_sum.is_finite() {
+        return Err(BatchError::Overflow);
+    }
+    let mut sizes = Vec::with_capacity(weights.len());
+    let mut remaining = total;
+
+    for (i, &weight) in weights.iter().enumerate() {
+        let size = if i == weights.len() - 1 {
+            remaining
+        } else {
+            ((total as f64 * (weight / weight_sum) + 0.5) as usize).min(remaining)
+        };
+        sizes.push(size);
+        remaining -= size;
+    }
+
+    non_empty(sizes)
+}
+
+/// Splits the total based on weights with the largest-remainder (Hamilton) method.
+///
+/// Every batch first receives the floor of its exact share `total * weight / weight_sum`. The
+/// units lost to flooring are then handed out one at a time to the batches with the largest
+/// fractional remainders, ties going to lower indices. Unlike [`split_weighted`], the rounding
+/// error is spread over the batches whose shares were rounded down the most instead of being
+/// absorbed by the last one. The arithmetic is exact, so the result does not depend on
+/// floating-point precision.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty.
+/// * Any weight is zero.
+/// * The total is too small to give every batch at least one unit.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_hamilton;
+///
+/// let batch_sizes = split_weighted_hamilton(100, &[1, 1, 1]).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![34, 33, 33]);
+/// ```
+pub fn split_weighted_hamilton(total: usize, weights: &[usize]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = weights.iter().position(|&weight| weight == 0) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+
+    non_empty(largest_remainder(total, weights))
+}
+
+/// Apportions `total` by the largest remainder method in exact `u128` arithmetic.
+///
+/// Each entry gets the floor of its exact share, and the units left over go to the largest
+/// fractional parts, lower indices first on ties. The weights must have a positive sum.
+pub(crate) fn largest_remainder(total: usize, weights: &[usize]) -> Vec<usize> {
+    let weights: Vec<u128> = weights.iter().map(|&w| w as u128).collect();
+    largest_remainder_u128(total, &weights)
+}
+
+/// [`largest_remainder`] over `u128` weights, each of which must be at most `u64::MAX` so
+/// that `total * weight` fits in a `u128`.
+fn largest_remainder_u128(total: usize, weights: &[u128]) -> Vec<usize> {
+    let weight_sum: u128 = weights.iter().sum();
+    let mut sizes = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+    for (i, &weight) in weights.iter().enumerate() {
+        let scaled = total as u128 * weight;
+        sizes.push((scaled / weight_sum) as usize);
+        remainders.push((i, scaled % weight_sum));
+    }
+
+    // Every floor is at most its exact share, so the floors fall short of `total` by less than
+    // one unit per entry.
+    let allocated: u128 = sizes.iter().map(|&size| size as u128).sum();
+    let leftover = (total as u128 - allocated) as usize;
+    debug_assert!(leftover < weights.len());
+    remainders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    for &(i, _) in remainders.iter().take(leftover) {
+        sizes[i] += 1;
+    }
+    sizes
+}
+
+/// Converts apportioned sizes into batches, failing if rounding left any batch empty.
+fn non_empty(sizes: impl IntoIterator<Item = usize>) -> Result<Vec<NonZeroUsize>, BatchError> {
+    sizes
+        .into_iter()
+        .map(|size| NonZeroUsize::new(size).ok_or_else(|| BatchError::ImpossibleConstraint(String::from("Total is too small to give every batch at least one unit"))))
+        .collect()
+}
+
+/// How [`split_weighted_rounded`] rounds each exact proportional share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rounding {
+    /// Round every share down.
+    Floor,
+    /// Round every share up.
+    Ceil,
+    /// Round every share to the nearest integer, halves up.
+    #[default]
+    Nearest,
+}
+
+/// Splits the total based on weights, rounding every share the same way.
+///
+/// Each batch's exact share `total * weight / weight_sum` is rounded according to `rounding`,
+/// computed exactly in `u128`. The rounded shares may then add up to more or less than the
+/// total, by fewer units than there are batches: `Floor` can only under-allocate, `Ceil` can
+/// only over-allocate, and `Nearest` can do either. The difference is reconciled one unit per
+/// batch, starting from the largest batches (lower indices first among equal sizes): an
+/// over-allocation, such as the one `Ceil` produces, is corrected by trimming one unit from each
+/// of the largest batches, and an under-allocation is made up by padding them. The largest
+/// batches absorb the correction because one unit is the smallest relative change to them.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+/// * `rounding` - How each exact share is rounded.
+///
+/// # Returns
+///
+/// A `Result` containing one batch size per weight, in the same order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty.
+/// * Any weight is zero.
+/// * The total is too small to give every batch at least one unit.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::{split_weighted_rounded, Rounding};
+///
+/// let sizes = |rounding| split_weighted_rounded(10, &[1, 1, 1], rounding).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+/// // Exact shares are 3.33 each: flooring leaves one unit to pad, ceiling two units to trim.
+/// assert_eq!(sizes(Rounding::Floor), vec![4, 3, 3]);
+/// assert_eq!(sizes(Rounding::Ceil), vec![3, 3, 4]);
+/// assert_eq!(sizes(Rounding::Nearest), vec![4, 3, 3]);
+/// ```
+pub fn split_weighted_rounded(total: usize, weights: &[usize], rounding: Rounding) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = weights.iter().position(|&weight| weight == 0) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+
+    let weight_sum: u128 = weights.iter().map(|&w| w as u128).sum();
+    let mut sizes: Vec<u128> = weights
+        .iter()
+        .map(|&weight| {
+            let scaled = total as u128 * weight as u128;
+            match rounding {
+                Rounding::Floor => scaled / weight_sum,
+                Rounding::Ceil => scaled.div_ceil(weight_sum),
+                Rounding::Nearest => (scaled + weight_sum / 2) / weight_sum,
+            }
+        })
+        .collect();
+
+    let allocated: u128 = sizes.iter().sum();
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].cmp(&sizes[a]).then(a.cmp(&b)));
+    if allocated > total as u128 {
+        for &i in order.iter().take((allocated - total as u128) as usize) {
+            sizes[i] -= 1;
+        }
+    } else {
+        for &i in order.iter().take((total as u128 - allocated) as usize) {
+            sizes[i] += 1;
+        }
+    }
+
+    non_empty(sizes.into_iter().map(|size| size as usize))
+}
+
+/// Splits the total into one batch per destination without exceeding any destination's cap.
+///
+/// Every batch first receives one unit, and the rest of the total is shared in proportion to
+/// each destination's remaining headroom (`cap - 1`) with the largest remainder method. Shares
+/// proportional to headroom never exceed it, so all destinations fill up at the same rate and
+/// reach their caps together when the total equals the sum of the caps.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `caps` - The largest batch each destination accepts.
+///
+/// # Returns
+///
+/// A `Result` containing one batch size per cap, in the same order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The caps slice is empty or contains a zero.
+/// * The total is smaller than the number of caps.
+/// * The total exceeds the sum of the caps.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_with_caps;
+///
+/// let batch_sizes = split_with_caps(41, &[10, 50, 20]).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![6, 25, 10]);
+/// assert!(split_with_caps(81, &[10, 50, 20]).is_err());
+/// ```
+pub fn split_with_caps(total: usize, caps: &[usize]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if caps.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Caps must not be empty")));
+    }
+    if caps.contains(&0) {
+        return Err(BatchError::InvalidInput(String::from("All caps must be positive numbers")));
+    }
+    if total < caps.len() {
+        return Err(BatchError::InsufficientTotal { total, min_required: caps.len() });
+    }
+    let capacity: u128 = caps.iter().map(|&cap| cap as u128).sum();
+    if total as u128 > capacity {
+        return Err(BatchError::ImpossibleConstraint(format!("A total of {} exceeds the combined capacity of {}", total, capacity)));
+    }
+
+    let headroom: Vec<usize> = caps.iter().map(|&cap| cap - 1).collect();
+    let excess = total - caps.len();
+    let shares = if excess == 0 { vec![0; caps.len()] } else { largest_remainder(excess, &headroom) };
+    Ok(shares.into_iter().map(|share| NonZeroUsize::MIN.saturating_add(share)).collect())
+}
+
+/// Splits the total based on weights, guaranteeing every batch a minimum size.
+///
+/// Every batch first receives `min` units, and the rest of the total
+/// (`total - min * weights.len()`) is shared in proportion to the weights with the largest
+/// remainder method. A weight too small to earn anything on its own therefore still gets `min`
+/// units instead of an empty batch.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+/// * `min` - The number of units reserved for every batch.
+///
+/// # Returns
+///
+/// A `Result` containing one batch size per weight, in the same order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty.
+/// * Any weight is zero.
+/// * The min is zero.
+/// * The total is smaller than `min * weights.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_min;
+///
+/// let batch_sizes = split_weighted_min(100, &[1, 1, 98], 10).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![11, 11, 78]);
+/// ```
+pub fn split_weighted_min(total: usize, weights: &[usize], min: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if let Some(index) = weights.iter().position(|&weight| weight == 0) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+    let min = NonZeroUsize::new(min).ok_or(BatchError::ZeroMinBatchSize)?;
+    let reserved = min.get().saturating_mul(weights.len());
+    if reserved > total {
+        return Err(BatchError::InsufficientTotal { total, min_required: reserved });
+    }
+
+    let excess = total - reserved;
+    let shares = if excess == 0 { vec![0; weights.len()] } else { largest_remainder(excess, weights) };
+    Ok(shares.into_iter().map(|share| min.saturating_add(share)).collect())
+}
+
+/// Splits the total based on weights, allowing batches that receive no units.
+///
+/// The sizes are computed exactly as in [`split_weighted`], but returned as plain `usize` so a
+/// weight too small to earn a unit yields a zero instead of an error. The result has one entry
+/// per weight, in the same order, and always sums to `total`. For totals that
+/// [`split_weighted`] accepts, both functions return the same sizes.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights for each batch.
+///
+/// # Returns
+///
+/// A `Result` containing one size per weight, possibly zero.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty.
+/// * Any weight is zero.
+/// * The sum of the weights, or the total multiplied by a weight, overflows `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_allow_empty;
+///
+/// assert_eq!(split_weighted_allow_empty(3, &[1, 1, 1, 1, 1]).unwrap(), vec![1, 1, 1, 0, 0]);
+/// ```
+pub fn split_weighted_allow_empty(total: usize, weights: &[usize]) -> Result<Vec<usize>, BatchError> {
+    weighted_sizes(total, weights)
+}
+
+/// Splits the total according to weights, then groups the weighted shares into a fixed number of
+/// batches.
+///
+/// The shares are first computed as in [`split_weighted_allow_empty`], one per weight. They are
+/// then consolidated greedily into `num_batches` contiguous groups, in weight order: each group
+/// aims for an equal part of what is left (`remaining / groups left`) and keeps taking the next
+/// share while that brings it at least as close to its aim, always taking at least one share
+/// and always leaving one share for every later group. The last group takes all remaining
+/// shares, so the sizes still add up to `total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `weights` - A slice of weights, one per shard.
+/// * `num_batches` - The number of batches to group the shards into.
+///
+/// # Returns
+///
+/// A `Result` containing one size per group, in weight order.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weights slice is empty, or any weight is zero.
+/// * The number of batches is zero or exceeds the number of weights.
+/// * A group would receive only shares rounded down to zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_into;
+///
+/// let batch_sizes = split_weighted_into(120, &[1, 1, 2, 2, 3, 3], 2).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![60, 60]);
+/// ```
+pub fn split_weighted_into(total: usize, weights: &[usize], num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    let shares = weighted_sizes(total, weights)?;
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if num_batches > shares.len() {
+        return Err(BatchError::InvalidInput(String::from("Number of batches must not exceed the number of weights")));
+    }
+
+    let mut batch_sizes = Vec::with_capacity(num_batches);
+    let mut remaining = total;
+    let mut next = 0;
+    for groups_left in (1..=num_batches).rev() {
+        let size = if groups_left == 1 {
+            remaining
+        } else {
+            let aim = remaining / groups_left;
+            let mut size = shares[next];
+            next += 1;
+            while shares.len() - next >= groups_left && (size + shares[next]).abs_diff(aim) <= size.abs_diff(aim) {
+                size += shares[next];
+                next += 1;
+            }
+            size
+        };
+        let size = NonZeroUsize::new(size)
+            .ok_or_else(|| BatchError::ImpossibleConstraint(format!("The weighted shares of {} cannot fill {} non-empty groups", total, num_batches)))?;
+        batch_sizes.push(size);
+        remaining -= size.get();
+    }
+
+    Ok(batch_sizes)
+}
+
+/// Splits the total so that each batch balances two independent weight dimensions.
+///
+/// Each batch carries a CPU weight and a memory weight, and a single allocation has to serve
+/// both. Each dimension is normalized to sum to one, so neither dominates because of the scale
+/// of its weights, and a batch's share is the mean of its CPU share and its memory share. That
+/// mean is the fixed point that iteratively averaging the two normalized dimensions converges to,
+/// so it is computed directly and no iteration cap is needed. The shares are then apportioned
+/// with the largest-remainder method so the sizes sum exactly to `total`.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `cpu_weights` - The CPU weight of each batch.
+/// * `mem_weights` - The memory weight of each batch.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The weight slices are empty or differ in length.
+/// * Any weight is zero.
+/// * The total is too small to give every batch at least one unit.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_weighted_2d;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_weighted_2d(100, &[1, 3], &[3, 1]).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(50).unwrap(); 2]);
+/// ```
+pub fn split_weighted_2d(total: usize, cpu_weights: &[usize], mem_weights: &[usize]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if cpu_weights.is_empty() {
+        return Err(BatchError::EmptyWeights);
+    }
+    if cpu_weights.len() != mem_weights.len() {
+        return Err(BatchError::InvalidInput(String::from("CPU and memory weights must have the same length")));
+    }
+    if let Some(index) = cpu_weights.iter().zip(mem_weights).position(|(&cpu, &mem)| cpu == 0 || mem == 0) {
+        return Err(BatchError::ZeroWeight { index });
+    }
+
+    let normalize = |weights: &[usize]| -> Vec<f64> {
+        let sum = weights.iter().map(|&w| w as f64).sum::<f64>();
+        weights.iter().map(|&w| w as f64 / sum).collect()
+    };
+    let shares: Vec<f64> = normalize(cpu_weights)
+        .into_iter()
+        .zip(normalize(mem_weights))
+        .map(|(cpu, mem)| (cpu + mem) / 2.0)
+        .collect();
+
+    non_empty(apportion_f64(total, &shares))
+}
+
+/// Splits a total into batches of geometrically decaying, non-increasing size.
+///
+/// Batch `i` is weighted `decay^i`, so each batch is `decay` times the previous one, and the
+/// shares are scaled to `total` with the largest-remainder method. Rounding never reorders the
+/// batches, so the sizes are non-increasing and the earliest (highest-priority) batches are the
+/// largest. With `decay = 1.0` the split is even.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `num_batches` - The number of batches to produce.
+/// * `decay` - The ratio between consecutive batch sizes, in `(0, 1]`.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of non-increasing `NonZeroUsize` batch sizes.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero.
+/// * The decay is not within `(0, 1]`.
+/// * A trailing batch would round down to zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_draining;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_draining(70, 3, 0.5).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(40).unwrap(), NonZeroUsize::new(20).unwrap(), NonZeroUsize::new(10).unwrap()]);
+/// ```
+pub fn split_draining(total: usize, num_batches: usize, decay: f64) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if !(decay > 0.0 && decay <= 1.0) {
+        return Err(BatchError::InvalidInput(String::from("Decay must be within (0, 1]")));
+    }
+
+    let mut weights = Vec::with_capacity(num_batches);
+    let mut weight = 1.0;
+    for _ in 0..num_batches {
+        weights.push(weight);
+        weight *= decay;
+    }
+    if weights.last().is_some_and(|&w| w <= 0.0) {
+        return Err(BatchError::ImpossibleConstraint(String::from("Decay is too steep for the number of batches")));
+    }
+
+    non_empty(apportion_f64(total, &weights))
+}
+
+/// Splits a total into batches whose sizes follow a geometric progression.
+///
+/// Batch `i` is weighted `ratio^i`, so with `ratio = 2.0` the sizes are proportional to
+/// `1, 2, 4, 8, ...`, and with a ratio below one they shrink instead, as in [`split_draining`].
+/// Every batch but the last receives the floor of its exact share, and the last batch absorbs
+/// what rounding leaves over, so the sizes add up to exactly `total`. The weights are computed
+/// relative to the largest one, so long progressions do not overflow.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `ratio` - The ratio between consecutive batch sizes, positive and finite.
+/// * `num_batches` - The number of batches to produce.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The number of batches is zero.
+/// * The ratio is zero, negative, infinite or NaN.
+/// * A batch would round down to zero.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_geometric;
+///
+/// let batch_sizes = split_geometric(150, 2.0, 4).unwrap();
+/// assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![10, 20, 40, 80]);
+/// ```
+pub fn split_geometric(total: usize, ratio: f64, num_batches: usize) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if total == 0 {
+        return Err(BatchError::ZeroTotal);
+    }
+    if num_batches == 0 {
+        return Err(BatchError::ZeroBatchCount);
+    }
+    if !(ratio > 0.0 && ratio.is_finite()) {
+        return Err(BatchError::InvalidInput(String::from("Ratio must be a finite positive number")));
+    }
+
+    // Walk from the largest batch towards the smallest so every weight is at most one.
+    let (step, growing) = if ratio > 1.0 { (1.0 / ratio, true) } else { (ratio, false) };
+    let mut weights = Vec::with_capacity(num_batches);
+    let mut weight = 1.0;
+    for _ in 0..num_batches {
+        weights.push(weight);
+        weight *= step;
+    }
+    if growing {
+        weights.reverse();
+    }
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut sizes = Vec::with_capacity(num_batches);
+    let mut remaining = total;
+    for &weight in &weights[..num_batches - 1] {
+        let size = ((total as f64 * weight / weight_sum) as usize).min(remaining);
+        sizes.push(size);
+        remaining -= size;
+    }
+    sizes.push(remaining);
+
+    non_empty(sizes)
+}
+
+/// Splits a total into balanced batches, giving the extra units to the most reliable batches.
+///
+/// The sizes are the same multiset as `split_by_count(total, reliabilities.len())`; only the
+/// placement of the `total % len` batches that carry one extra unit differs. Those go to the
+/// batches with the highest reliability scores, with ties broken toward lower indices.
+///
+/// # Arguments
+///
+/// * `total` - The total number to be split.
+/// * `reliabilities` - The reliability score of each batch, in `[0, 1]`.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of `NonZeroUsize` representing the size of each batch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// * The total is zero.
+/// * The reliabilities slice is empty.
+/// * Any score is NaN or outside `[0, 1]`.
+/// * The total is smaller than the number of batches.
+///
+/// # Examples
+///
+/// ```
+/// use rsbatch_maestro::split_reliability_biased;
+/// use std::num::NonZeroUsize;
+///
+/// let batch_sizes = split_reliability_biased(10, &[0.5, 0.99, 0.7]).unwrap();
+/// assert_eq!(batch_sizes, vec![NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap()]);
+/// ```
+pub fn split_reliability_biased(total: usize, reliabilities: &[f64]) -> Result<Vec<NonZeroUsize>, BatchError> {
+    if reliabilities.is_empty() {
+        return Err(BatchError::InvalidInput(String::from("Reliabilities slice must not be empty")));
+    }
+    if reliabilities.iter().any(|r| !(0.0..=1.0).contains(r)) {
+        return Err(BatchError::InvalidInput(String::from("All reliability scores must be within [0, 1]")));
+    }
+    let balanced = split_by_count(total, reliabilities.len())?;
+
+    let base_size = balanced[balanced.len() - 1];
+    let remainder = total % reliabilities.len();
+    let mut order: Vec<usize> = (0..reliabilities.len()).collect();
+    order.sort_by(|&a, &b| reliabilities[b].total_cmp(&reliabilities[a]).then(a.cmp(&b)));
+
+    let mut batch_sizes = vec![base_size; reliabilities.len()];
+    for &i in order.iter().take(remainder) {
+        batch_sizes[i] = base_size.saturating_add(1);
+    }
+
+    Ok(batch_sizes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_weighted_into() {
+        let weights = [5, 1, 1, 3, 2, 2, 4, 1, 1, 1, 2, 3];
+        for num_batches in 1..=weights.len() {
+            let batch_sizes = split_weighted_into(1000, &weights, num_batches).unwrap();
+            assert_eq!(batch_sizes.len(), num_batches);
+            assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 1000);
+        }
+        let shares = split_weighted_allow_empty(1000, &weights).unwrap();
+        let grouped = split_weighted_into(1000, &weights, weights.len()).unwrap();
+        assert_eq!(grouped.iter().map(|b| b.get()).collect::<Vec<_>>(), shares);
+        assert_eq!(split_weighted_into(1000, &weights, 4).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>(), vec![268, 269, 230, 233]);
+    }
+
+    #[test]
+    fn test_split_weighted_into_errors() {
+        assert_eq!(split_weighted_into(0, &[1, 2], 1), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_into(10, &[1, 0], 1), Err(BatchError::ZeroWeight { index: 1 }));
+        assert_eq!(split_weighted_into(10, &[1, 2], 0), Err(BatchError::ZeroBatchCount));
+        assert!(matches!(split_weighted_into(10, &[1, 2], 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_weighted_into(3, &[1, 1, 1000], 2), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_with_caps() {
+        let caps = [10, 50, 20, 1, 7];
+        for total in caps.len()..=88 {
+            let batch_sizes = split_with_caps(total, &caps).unwrap();
+            assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+            assert!(batch_sizes.iter().zip(&caps).all(|(size, &cap)| size.get() <= cap));
+        }
+        let full = split_with_caps(88, &caps).unwrap();
+        assert_eq!(full.iter().map(|b| b.get()).collect::<Vec<_>>(), caps);
+        assert_eq!(split_with_caps(3, &[1, 1, 1]).unwrap(), vec![NonZeroUsize::MIN; 3]);
+        assert_eq!(split_with_caps(usize::MAX, &[usize::MAX, usize::MAX]).unwrap().iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+    }
+
+    #[test]
+    fn test_split_with_caps_errors() {
+        assert_eq!(split_with_caps(0, &[3]), Err(BatchError::ZeroTotal));
+        assert!(matches!(split_with_caps(3, &[]), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_with_caps(3, &[3, 0]), Err(BatchError::InvalidInput(_))));
+        assert_eq!(split_with_caps(1, &[3, 3]), Err(BatchError::InsufficientTotal { total: 1, min_required: 2 }));
+        assert!(matches!(split_with_caps(7, &[3, 3]), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_weighted_min() {
+        let weights = [1, 1000, 3, 1];
+        for min in 1..6 {
+            for total in min * weights.len()..200 {
+                let batch_sizes = split_weighted_min(total, &weights, min).unwrap();
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                assert!(batch_sizes.iter().all(|b| b.get() >= min));
+                assert!(batch_sizes[1] >= batch_sizes[2] && batch_sizes[2] >= batch_sizes[3]);
+            }
+        }
+        assert_eq!(split_weighted_min(6, &[5, 1], 3).unwrap(), vec![NonZeroUsize::new(3).unwrap(); 2]);
+        assert_eq!(split_weighted_min(usize::MAX, &[1, 1], 1).unwrap().iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+    }
+
+    #[test]
+    fn test_split_weighted_min_errors() {
+        assert_eq!(split_weighted_min(0, &[1], 1), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_min(10, &[], 1), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted_min(10, &[1, 0], 1), Err(BatchError::ZeroWeight { index: 1 }));
+        assert_eq!(split_weighted_min(10, &[1, 1], 0), Err(BatchError::ZeroMinBatchSize));
+        assert_eq!(split_weighted_min(10, &[1, 1, 1], 4), Err(BatchError::InsufficientTotal { total: 10, min_required: 12 }));
+        assert_eq!(split_weighted_min(10, &[1, 1], usize::MAX), Err(BatchError::InsufficientTotal { total: 10, min_required: usize::MAX }));
+    }
+
+    #[test]
+    fn test_split_by_speed() {
+        let batches = split_by_speed(600, &[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(batches.iter().map(|b| b.get()).sum::<usize>(), 600);
+        assert_eq!(batches[2].get(), 3 * batches[0].get());
+        assert_eq!(split_by_speed(10, &[1.0, 1.0, 1.0]), Ok(vec![NonZeroUsize::new(4).unwrap(), NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(3).unwrap()]));
+    }
+
+    #[test]
+    fn test_split_by_speed_uneven_ratio() {
+        let batches = split_by_speed(100, &[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(batches.iter().map(|b| b.get()).sum::<usize>(), 100);
+        let ratio = batches[2].get() as f64 / batches[0].get() as f64;
+        assert!((ratio - 3.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_apportion_f64_large_totals() {
+        let half = 1 << 61;
+        assert_eq!(apportion_f64((1 << 62) - 1, &[1.0, 1.0]), vec![half, half - 1]);
+        assert_eq!(apportion_f64(usize::MAX, &[1.0, 1.0]), vec![usize::MAX / 2 + 1, usize::MAX / 2]);
+        let batches = split_by_speed(usize::MAX, &[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(batches.iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+        let batches = split_weighted_2d(usize::MAX, &[1, 3], &[2, 5]).unwrap();
+        assert_eq!(batches.iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+        let batches = split_draining(usize::MAX, 3, 0.5).unwrap();
+        assert_eq!(batches.iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+    }
+
+    #[test]
+    fn test_split_by_speed_errors() {
+        assert!(split_by_speed(0, &[1.0]).is_err());
+        assert_eq!(split_by_speed(10, &[]), Err(BatchError::EmptyWeights));
+        assert_eq!(split_by_speed(10, &[1.0, 0.0]), Err(BatchError::InvalidWeight { index: 1 }));
+        assert_eq!(split_by_speed(10, &[-2.0, 1.0]), Err(BatchError::InvalidWeight { index: 0 }));
+        assert_eq!(split_by_speed(10, &[1.0, f64::NAN]), Err(BatchError::InvalidWeight { index: 1 }));
+        assert_eq!(split_by_speed(10, &[1.0, f64::INFINITY]), Err(BatchError::InvalidWeight { index: 1 }));
+        assert!(split_by_speed(2, &[1.0, 1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_split_weighted_annotated() {
+        let annotated = split_weighted_annotated(100, &[1, 2, 3]).unwrap();
+        assert_eq!(annotated.iter().map(|&(w, _)| w).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(annotated.iter().map(|&(_, s)| s.get()).sum::<usize>(), 100);
+        assert_eq!(annotated.iter().map(|&(_, s)| s).collect::<Vec<_>>(), split_weighted(100, vec![1, 2, 3]).unwrap());
+    }
+
+    #[test]
+    fn test_split_weighted_annotated_errors() {
+        assert!(split_weighted_annotated(0, &[1, 2]).is_err());
+        assert!(split_weighted_annotated(10, &[]).is_err());
+        assert!(split_weighted_annotated(10, &[1, 0]).is_err());
+    }
+
+    #[test]
+    fn test_split_weighted_2d_compromise() {
+        let batch_sizes = split_weighted_2d(120, &[1, 1, 4], &[4, 1, 1]).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 120);
+        let cpu_only = split_weighted(120, vec![1, 1, 4]).unwrap();
+        let mem_only = split_weighted(120, vec![4, 1, 1]).unwrap();
+        for i in 0..3 {
+            let (low, high) = (cpu_only[i].min(mem_only[i]), cpu_only[i].max(mem_only[i]));
+            assert!(low <= batch_sizes[i] && batch_sizes[i] <= high);
+        }
+        assert_eq!(batch_sizes, vec![NonZeroUsize::new(50).unwrap(), NonZeroUsize::new(20).unwrap(), NonZeroUsize::new(50).unwrap()]);
+    }
+
+    #[test]
+    fn test_split_weighted_2d_errors() {
+        assert!(split_weighted_2d(0, &[1], &[1]).is_err());
+        assert!(split_weighted_2d(10, &[], &[]).is_err());
+        assert!(split_weighted_2d(10, &[1, 2], &[1]).is_err());
+        assert!(split_weighted_2d(10, &[1, 0], &[1, 1]).is_err());
+        assert!(split_weighted_2d(10, &[1, 1], &[0, 1]).is_err());
+        assert!(split_weighted_2d(1, &[1, 1], &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_split_draining() {
+        let batch_sizes = split_draining(1000, 5, 0.5).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 1000);
+        assert!(batch_sizes.windows(2).all(|pair| pair[0] > pair[1]));
+        for pair in batch_sizes.windows(2) {
+            assert!(pair[0].get().abs_diff(2 * pair[1].get()) <= 2);
+        }
+    }
+
+    #[test]
+    fn test_split_draining_even_and_monotonic() {
+        assert_eq!(split_draining(10, 3, 1.0), split_by_speed(10, &[1.0, 1.0, 1.0]));
+        let batch_sizes = split_draining(101, 7, 0.9).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), 101);
+        assert!(batch_sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn test_split_draining_errors() {
+        assert!(split_draining(0, 3, 0.5).is_err());
+        assert!(split_draining(10, 0, 0.5).is_err());
+        assert!(split_draining(10, 3, 0.0).is_err());
+        assert!(split_draining(10, 3, 1.5).is_err());
+        assert!(split_draining(10, 3, f64::NAN).is_err());
+        assert!(split_draining(10, 5, 0.1).is_err());
+    }
+
+    #[test]
+    fn test_split_geometric() {
+        let sizes = |batches: Vec<NonZeroUsize>| batches.iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(sizes(split_geometric(15, 2.0, 4).unwrap()), vec![1, 2, 4, 8]);
+        assert_eq!(sizes(split_geometric(70, 0.5, 3).unwrap()), vec![40, 20, 10]);
+        assert_eq!(sizes(split_geometric(10, 1.0, 3).unwrap()), vec![3, 3, 4]);
+        for total in 100..300 {
+            for num_batches in 1..5 {
+                let batch_sizes = split_geometric(total, 1.5, num_batches).unwrap();
+                assert_eq!(batch_sizes.len(), num_batches);
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                assert!(batch_sizes.windows(2).all(|pair| pair[0] <= pair[1]));
+            }
+        }
+        let long = split_geometric(usize::MAX, 10.0, 400);
+        assert!(matches!(long, Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_geometric_errors() {
+        assert_eq!(split_geometric(0, 2.0, 3), Err(BatchError::ZeroTotal));
+        assert_eq!(split_geometric(10, 2.0, 0), Err(BatchError::ZeroBatchCount));
+        assert!(matches!(split_geometric(10, 0.0, 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_geometric(10, -2.0, 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_geometric(10, f64::INFINITY, 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_geometric(10, f64::NAN, 3), Err(BatchError::InvalidInput(_))));
+        assert!(matches!(split_geometric(10, 2.0, 5), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_reliability_biased() {
+        let batch_sizes = split_reliability_biased(10, &[0.2, 0.9, 0.5]).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![3, 4, 3]);
+
+        let batch_sizes = split_reliability_biased(17, &[0.1, 0.8, 0.3, 0.8, 0.95]).unwrap();
+        assert_eq!(batch_sizes.iter().map(|b| b.get()).collect::<Vec<_>>(), vec![3, 4, 3, 3, 4]);
+
+        let mut sorted = batch_sizes.clone();
+        let mut expected = split_by_count(17, 5).unwrap();
+        sorted.sort();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_split_reliability_biased_errors() {
+        assert!(split_reliability_biased(0, &[0.5]).is_err());
+        assert!(split_reliability_biased(10, &[]).is_err());
+        assert!(split_reliability_biased(10, &[0.5, 1.5]).is_err());
+        assert!(split_reliability_biased(10, &[-0.1, 0.5]).is_err());
+        assert!(split_reliability_biased(10, &[f64::NAN, 0.5]).is_err());
+        assert!(split_reliability_biased(2, &[0.5, 0.5, 0.5]).is_err());
+    }
+
+    #[test]
+    fn test_split_weighted_f64() {
+        let sizes = |total, weights: &[f64]| split_weighted_f64(total, weights).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(sizes(100, &[0.1, 0.25, 0.65]), vec![10, 25, 65]);
+        assert_eq!(sizes(100, &[1.0, 1.0, 1.0]), vec![33, 33, 34]);
+        assert_eq!(sizes(7, &[0.5]), vec![7]);
+        let drifting = sizes(1001, &[0.3, 0.3, 0.3, 0.1]);
+        assert_eq!(drifting.iter().sum::<usize>(), 1001);
+        assert_eq!(sizes(100, &[2.0, 6.0]), split_weighted(100, vec![2, 6]).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_weighted_f64_errors() {
+        assert_eq!(split_weighted_f64(0, &[1.0]), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_f64(10, &[]), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted_f64(10, &[1.0, f64::NAN]), Err(BatchError::InvalidWeight { index: 1 }));
+        assert_eq!(split_weighted_f64(10, &[f64::INFINITY]), Err(BatchError::InvalidWeight { index: 0 }));
+        assert_eq!(split_weighted_f64(10, &[1.0, -0.5]), Err(BatchError::InvalidWeight { index: 1 }));
+        assert_eq!(split_weighted_f64(10, &[1.0, 0.0]), Err(BatchError::ZeroWeight { index: 1 }));
+        assert_eq!(split_weighted_f64(10, &[f64::MAX, f64::MAX]), Err(BatchError::Overflow));
+        assert!(split_weighted_f64(2, &[1.0, 1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn test_split_weighted_hamilton() {
+        let sizes = |total, weights: &[usize]| split_weighted_hamilton(total, weights).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+        assert_eq!(sizes(100, &[1, 1, 1]), vec![34, 33, 33]);
+        assert_eq!(sizes(10, &[1, 1, 1, 1]), vec![3, 3, 2, 2]);
+        // Exact shares 14.2, 28.4, 42.6, 14.8: the two largest fractions get the extra units.
+        assert_eq!(sizes(100, &[142, 284, 426, 148]), vec![14, 28, 43, 15]);
+        assert_eq!(sizes(usize::MAX, &[usize::MAX, usize::MAX]), vec![usize::MAX / 2 + 1, usize::MAX / 2]);
+    }
+
+    #[test]
+    fn test_split_weighted_hamilton_errors() {
+        assert_eq!(split_weighted_hamilton(0, &[1]), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_hamilton(10, &[]), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted_hamilton(10, &[1, 0]), Err(BatchError::ZeroWeight { index: 1 }));
+        assert!(split_weighted_hamilton(2, &[1, 1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_split_weighted_rounded() {
+        let weights = [142, 284, 426, 148, 50, 7];
+        for rounding in [Rounding::Floor, Rounding::Ceil, Rounding::Nearest] {
+            for total in 200..700 {
+                let batch_sizes = split_weighted_rounded(total, &weights, rounding).unwrap();
+                assert_eq!(batch_sizes.iter().map(|b| b.get()).sum::<usize>(), total);
+                for (size, &weight) in batch_sizes.iter().zip(&weights) {
+                    let exact = total as f64 * weight as f64 / 1057.0;
+                    assert!((size.get() as f64 - exact).abs() < 2.0);
+                }
+            }
+        }
+        let sizes = |total, rounding| split_weighted_rounded(total, &[1, 3], rounding).unwrap().iter().map(|b| b.get()).collect::<Vec<_>>();
+        // Exact shares 2.5 and 7.5.
+        assert_eq!(sizes(10, Rounding::Floor), vec![2, 8]);
+        assert_eq!(sizes(10, Rounding::Ceil), vec![3, 7]);
+        assert_eq!(sizes(10, Rounding::Nearest), vec![3, 7]);
+        let large = split_weighted_rounded(usize::MAX, &[usize::MAX, usize::MAX], Rounding::Ceil).unwrap();
+        assert_eq!(large.iter().map(|b| b.get() as u128).sum::<u128>(), usize::MAX as u128);
+    }
+
+    #[test]
+    fn test_split_weighted_rounded_errors() {
+        assert_eq!(split_weighted_rounded(0, &[1], Rounding::Floor), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_rounded(10, &[], Rounding::Floor), Err(BatchError::EmptyWeights));
+        assert_eq!(split_weighted_rounded(10, &[1, 0], Rounding::Ceil), Err(BatchError::ZeroWeight { index: 1 }));
+        assert!(matches!(split_weighted_rounded(2, &[1, 1, 1], Rounding::Nearest), Err(BatchError::ImpossibleConstraint(_))));
+        assert!(matches!(split_weighted_rounded(3, &[1, 100], Rounding::Floor), Err(BatchError::ImpossibleConstraint(_))));
+    }
+
+    #[test]
+    fn test_split_weighted_allow_empty() {
+        assert_eq!(split_weighted_allow_empty(3, &[1, 1, 1, 1, 1]).unwrap(), vec![1, 1, 1, 0, 0]);
+        assert_eq!(split_weighted_allow_empty(10, &[1, 100]).unwrap(), vec![0, 10]);
+        assert_eq!(split_weighted_allow_empty(10, &[100, 1]).unwrap(), vec![10, 0]);
+        for (total, weights) in [(100, vec![1, 2, 3]), (2, vec![1, 1, 1]), (1, vec![5; 9])] {
+            let sizes = split_weighted_allow_empty(total, &weights).unwrap();
+            assert_eq!(sizes.len(), weights.len());
+            assert_eq!(sizes.iter().sum::<usize>(), total);
+        }
+        let nonzero: Vec<usize> = split_weighted(100, vec![1, 2, 3]).unwrap().iter().map(|b| b.get()).collect();
+        assert_eq!(split_weighted_allow_empty(100, &[1, 2, 3]).unwrap(), nonzero);
+        assert_eq!(split_weighted_allow_empty(0, &[1]), Err(BatchError::ZeroTotal));
+        assert_eq!(split_weighted_allow_empty(5, &[]), Err(BatchError::EmptyWeights));
+    }
+}